@@ -2,15 +2,24 @@
 
 use ink_lang as ink;
 
+mod psp22;
+mod psp34;
+
 #[ink::contract]
 mod staking_rewards {
-	use ink_prelude::{vec, vec::Vec};
+	use crate::{
+		psp22,
+		psp34::{Id, PSP34Error, PSP34},
+	};
+	use ink_prelude::{format, vec, vec::Vec};
+	use ink_primitives::KeyPtr;
 	use ink_storage::{
 		traits::{PackedLayout, SpreadAllocate, SpreadLayout},
 		Mapping,
 	};
 
 	const MIL: u128 = 1_000_000;
+	const LOG2_FRACTION_BITS: u32 = 30;
 
 	#[ink(event)]
 	pub struct DepositSuccessful {
@@ -22,12 +31,110 @@ mod staking_rewards {
 	pub struct RedeemSuccessful {
 		staker: AccountId,
 		lock_box: LockBox,
+		reward: Balance,
 	}
 
 	#[ink(event)]
 	pub struct WithdrawSuccessful {
 		staker: AccountId,
 		lock_box: LockBox,
+		reward: Balance,
+	}
+
+	#[ink(event)]
+	pub struct PartialWithdrawSuccessful {
+		staker: AccountId,
+		lock_box: LockBox,
+		principal: Balance,
+		interest: Balance,
+		reward: Balance,
+	}
+
+	#[ink(event)]
+	pub struct ContractConfigured {
+		old: ContractConfig,
+		new: ContractConfig,
+	}
+
+	#[ink(event)]
+	pub struct PauseChanged {
+		account: AccountId,
+		old: bool,
+		new: bool,
+	}
+
+	#[ink(event)]
+	pub struct LockBoxSet {
+		old: LockBox,
+		new: LockBox,
+	}
+
+	#[ink(event)]
+	pub struct TierSet {
+		id: u32,
+		old: Option<Tier>,
+		new: Tier,
+	}
+
+	#[ink(event)]
+	pub struct RewardsFunded {
+		account: AccountId,
+		amount: Balance,
+		old_reward_reserve: Balance,
+		new_reward_reserve: Balance,
+	}
+
+	#[ink(event)]
+	pub struct Refunded {
+		account: AccountId,
+		amount: Balance,
+		old_reward_reserve: Balance,
+		new_reward_reserve: Balance,
+	}
+
+	#[ink(event)]
+	pub struct CodeUpgraded {
+		old: Option<[u8; 32]>,
+		new: [u8; 32],
+	}
+
+	#[ink(event)]
+	pub struct OwnershipTransferStarted {
+		owner: AccountId,
+		pending_owner: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct OwnershipTransferred {
+		previous_owner: AccountId,
+		new_owner: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct RoleGranted {
+		role: Role,
+		account: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct RoleRevoked {
+		role: Role,
+		account: AccountId,
+	}
+
+	#[ink(event)]
+	pub struct Transfer {
+		from: Option<AccountId>,
+		to: Option<AccountId>,
+		id: Id,
+	}
+
+	#[ink(event)]
+	pub struct Approval {
+		owner: AccountId,
+		operator: AccountId,
+		id: Option<Id>,
+		approved: bool,
 	}
 
 	#[derive(
@@ -39,12 +146,115 @@ mod staking_rewards {
 	)]
 	pub struct LockBox {
 		id: u128,
+		tier: u32,
 		created_at: Timestamp,
 		deposit: Balance,
 		interest: Balance,
 		release: Timestamp,
 	}
 
+	#[derive(
+		Clone, Copy, Debug, PartialEq, scale::Decode, scale::Encode, PackedLayout, SpreadLayout,
+	)]
+	#[cfg_attr(
+		feature = "std",
+		derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+	)]
+	pub struct Tier {
+		locking_duration: u64,
+		base_interest_percent_permil: u128,
+		capacity: u128,
+		total_liquidity: u128,
+		lock_boxes_count: u128,
+	}
+
+	#[derive(Clone, Copy, Debug, PartialEq, scale::Decode, scale::Encode)]
+	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+	pub struct TierMetadata {
+		id: u32,
+		tier: Tier,
+		dynamic_interest_percent_permil: u128,
+	}
+
+	/// Shape of the negative interest in the number of sampled stakers and liquidity
+	#[derive(
+		Clone,
+		Copy,
+		Debug,
+		Default,
+		PartialEq,
+		Eq,
+		scale::Decode,
+		scale::Encode,
+		PackedLayout,
+		SpreadLayout,
+	)]
+	#[cfg_attr(
+		feature = "std",
+		derive(scale_info::TypeInfo, ink_storage::traits::StorageLayout)
+	)]
+	pub enum InterestCurve {
+		/// `log2(1 + samples)`
+		#[default]
+		Logarithmic,
+		/// `samples`
+		Linear,
+		/// `floor(log2(1 + samples))`, dropping each time the samples double
+		Step,
+	}
+
+	impl InterestCurve {
+		fn apply_permil(&self, samples: u128) -> u128 {
+			match self {
+				InterestCurve::Logarithmic => log2_permil(samples.saturating_add(1)),
+				InterestCurve::Linear => samples.saturating_mul(MIL),
+				InterestCurve::Step => log2_permil(samples.saturating_add(1)) / MIL * MIL,
+			}
+		}
+	}
+
+	impl SpreadAllocate for InterestCurve {
+		fn allocate_spread(ptr: &mut KeyPtr) -> Self {
+			ptr.next_for::<Self>();
+			Self::default()
+		}
+	}
+
+	/// Values changed by `configure_contract`
+	#[derive(Clone, Copy, Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+	pub struct ContractConfig {
+		max_deposit_value: u128,
+		max_total_liquidity: u128,
+		deposit_deadline: u64,
+		stakers_sample: u128,
+		liquidity_sample: u128,
+		negative_interest_multiplier_permil: u128,
+		interest_curve: InterestCurve,
+		early_withdraw_penalty_percent_permil: u128,
+	}
+
+	/// Permissions the owner can grant to other accounts
+	#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
+	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+	pub enum Role {
+		/// Configures the contract, its tiers and lock boxes
+		ConfigAdmin,
+		/// Pauses and resumes the contract
+		Pauser,
+		/// Funds the reward reserve and refunds the free balance
+		Treasurer,
+		/// Upgrades the contract code
+		Upgrader,
+	}
+
+	const ROLES: [Role; 4] = [
+		Role::ConfigAdmin,
+		Role::Pauser,
+		Role::Treasurer,
+		Role::Upgrader,
+	];
+
 	#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
 	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 	pub enum Error {
@@ -55,54 +265,134 @@ mod staking_rewards {
 		LockBoxNotFound,
 		LockBoxNotReleased,
 		TooManyStakers,
+		TierNotFound,
+		TierCapacityReached,
+		InvalidAmount,
+		NotOwner,
+		Paused,
+		SelfCall,
+		InsufficientContractBalance,
+		TransferFailed,
+		SetCodeHashFailed,
+		InsufficientRewardReserve,
+		RewardTransferFailed,
+		MissingRole,
+		NotPendingOwner,
+		InvalidLockBoxChange,
 	}
 
 	#[ink(storage)]
 	#[derive(Default, SpreadAllocate)]
 	pub struct StakingRewards {
 		owner: AccountId,
+		pending_owner: Option<AccountId>,
+		roles: Mapping<(Role, AccountId), bool>,
 		max_deposit_value: u128,
 		min_deposit_value: u128,
 		max_total_liquidity: u128,
 		max_stakers: u128,
-		locking_duration: u64,
 		deposit_deadline: u64,
-		base_interest_percent_permil: u128,
 		stakers_sample: u128,
 		liquidity_sample: u128,
 		negative_interest_multiplier_permil: u128,
+		interest_curve: InterestCurve,
+		early_withdraw_penalty_percent_permil: u128,
 		total_liquidity: u128,
 		claimed_rewards: u128,
 		unclaimed_rewards: u128,
+		reward_reserve: u128,
+		reward_token: Option<AccountId>,
+		reward_rate_permil: u128,
+		reserved_reward_tokens: u128,
 		stakers_count: u128,
+		tiers: Mapping<u32, Tier>,
+		tiers_count: u32,
 		user_boxes: Mapping<AccountId, Vec<u128>>,
 		lock_boxes: Mapping<u128, LockBox>,
+		box_owners: Mapping<u128, AccountId>,
+		box_approvals: Mapping<u128, AccountId>,
+		operator_approvals: Mapping<(AccountId, AccountId), bool>,
 		lock_box_counter: u128,
 		is_paused: bool,
+		code_hash: Option<[u8; 32]>,
 	}
 
-	#[derive(Clone, Copy, Debug, PartialEq, scale::Decode, scale::Encode)]
+	#[derive(Clone, Debug, PartialEq, scale::Decode, scale::Encode)]
 	#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 	pub struct Metadata {
 		owner: AccountId,
+		pending_owner: Option<AccountId>,
 		max_deposit_value: u128,
 		min_deposit_value: u128,
 		max_total_liquidity: u128,
 		max_stakers: u128,
-		locking_duration: u64,
 		deposit_deadline: u64,
-		base_interest_percent_permil: u128,
 		stakers_sample: u128,
 		liquidity_sample: u128,
 		negative_interest_multiplier_permil: u128,
+		interest_curve: InterestCurve,
+		early_withdraw_penalty_percent_permil: u128,
 		total_liquidity: u128,
 		claimed_rewards: u128,
 		unclaimed_rewards: u128,
+		reward_reserve: u128,
+		reward_token: Option<AccountId>,
+		reward_rate_permil: u128,
+		reserved_reward_tokens: u128,
 		stakers_count: u128,
-		dynamic_interest_percent_permil: u128,
+		tiers: Vec<TierMetadata>,
 		is_paused: bool,
 	}
 
+	/// `a * b / c` falling back to a less precise order when the product overflows
+	fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+		if c == 0 {
+			return 0;
+		}
+
+		match a.checked_mul(b) {
+			Some(product) => product / c,
+			None => (a / c).saturating_mul(b),
+		}
+	}
+
+	/// Reverts all storage changes of the current message and returns `Err(error)` to the caller
+	fn revert(error: Error) -> ! {
+		ink_env::return_value::<Result<(), Error>>(
+			ink_env::ReturnFlags::default().set_reverted(true),
+			&Err(error),
+		)
+	}
+
+	/// Fixed-point `log2(num)` scaled by `MIL`, rounded down, `0` for `num == 0`
+	fn log2_permil(num: u128) -> u128 {
+		if num == 0 {
+			return 0;
+		}
+
+		let integer_part = 127 - num.leading_zeros();
+
+		// Normalize `num` into [1, 2) with 63 fractional bits
+		let mut y = if integer_part > 63 {
+			num >> (integer_part - 63)
+		} else {
+			num << (63 - integer_part)
+		};
+
+		// Each squaring of y in [1, 2) yields the next binary digit of the fraction
+		let mut fraction: u128 = 0;
+		for _ in 0..LOG2_FRACTION_BITS {
+			y = (y * y) >> 63;
+			fraction <<= 1;
+			if y >= 1 << 64 {
+				y >>= 1;
+				fraction |= 1;
+			}
+		}
+
+		integer_part as u128 * MIL + ((fraction * MIL) >> LOG2_FRACTION_BITS)
+	}
+
 	impl StakingRewards {
 		#[ink(constructor, payable)]
 		pub fn new(
@@ -116,35 +406,52 @@ mod staking_rewards {
 			stakers_sample: u128,
 			liquidity_sample: u128,
 			negative_interest_multiplier_permil: u128,
+			reward_token: Option<AccountId>,
+			reward_rate_permil: u128,
 		) -> Self {
 			ink_lang::utils::initialize_contract(|contract: &mut Self| {
 				contract.owner = Self::env().caller();
+				contract.pending_owner = None;
+				for role in ROLES {
+					contract.set_role(role, contract.owner, true);
+				}
 				contract.max_deposit_value = max_deposit_value;
 				contract.min_deposit_value = min_deposit_value;
 				contract.max_total_liquidity = max_total_liquidity;
 				contract.max_stakers = max_stakers;
-				contract.locking_duration = locking_duration;
 				contract.deposit_deadline =
 					Self::env().block_timestamp() + duration_until_deposit_deadline;
-				contract.base_interest_percent_permil = base_interest_percent_permil;
 				contract.stakers_sample = stakers_sample;
 				contract.liquidity_sample = liquidity_sample;
 				contract.negative_interest_multiplier_permil = negative_interest_multiplier_permil;
+				contract.interest_curve = InterestCurve::Logarithmic;
+				contract.early_withdraw_penalty_percent_permil = 100 * MIL;
 				contract.total_liquidity = 0;
 				contract.claimed_rewards = 0;
 				contract.unclaimed_rewards = 0;
+				contract.reward_reserve = Self::env().transferred_value();
+				contract.reward_token = reward_token;
+				contract.reward_rate_permil = reward_rate_permil;
+				contract.reserved_reward_tokens = 0;
 				contract.stakers_count = 0;
 				contract.lock_box_counter = 0;
 				contract.is_paused = false;
+				contract.code_hash = None;
+				contract.tiers_count = 0;
+				contract.insert_tier(
+					locking_duration,
+					base_interest_percent_permil,
+					max_total_liquidity,
+				);
 			})
 		}
 
 		#[ink(message, payable)]
-		pub fn deposit(&mut self) -> Result<LockBox, Error> {
+		pub fn deposit(&mut self, tier_id: u32) -> Result<LockBox, Error> {
 			let caller = self.env().caller();
-			self.ensure_not_self_account(&caller);
+			self.ensure_not_self_account(&caller)?;
 
-			self.ensure_not_paused();
+			self.ensure_not_paused()?;
 
 			let now = self.env().block_timestamp();
 			if now > self.deposit_deadline {
@@ -162,16 +469,32 @@ mod staking_rewards {
 				return Err(Error::MaxTotalLiquidityReached);
 			}
 
+			let mut tier = self.tiers.get(tier_id).ok_or(Error::TierNotFound)?;
+			if value > tier.capacity.saturating_sub(tier.total_liquidity) {
+				return Err(Error::TierCapacityReached);
+			}
+
 			let lock_box = LockBox {
 				id: self.lock_box_counter,
+				tier: tier_id,
 				created_at: now,
 				deposit: value,
-				interest: value * self.interest_percent_permil() / MIL / 100,
-				release: now + self.locking_duration,
+				interest: mul_div(value, self.interest_percent_permil(&tier), 100 * MIL),
+				release: now + tier.locking_duration,
 			};
 
+			// Checked before any storage is written, as storage is kept when the message fails
+			if self.user_boxes.get(&caller).is_none() && self.stakers_count >= self.max_stakers {
+				return Err(Error::TooManyStakers);
+			}
+			self.reserve_interest(lock_box.interest)?;
+
 			self.add_box(&caller, &lock_box)?;
 
+			tier.total_liquidity += value;
+			tier.lock_boxes_count += 1;
+			self.tiers.insert(tier_id, &tier);
+
 			self.total_liquidity += value;
 			self.unclaimed_rewards += lock_box.interest;
 			self.lock_box_counter += 1;
@@ -187,28 +510,23 @@ mod staking_rewards {
 		#[ink(message)]
 		pub fn redeem(&mut self, lock_box_id: u128) -> Result<Balance, Error> {
 			let caller = self.env().caller();
-			self.ensure_not_self_account(&caller);
-
-			self.ensure_not_paused();
-
-			let lock_box = self.remove_box(&caller, lock_box_id, true);
-			if lock_box.is_err() {
-				return Err(Error::LockBoxNotFound);
-			}
+			self.ensure_not_self_account(&caller)?;
 
-			let lock_box = lock_box.unwrap();
+			self.ensure_not_paused()?;
 
-			let amount = lock_box.deposit + lock_box.interest;
+			let lock_box = self.remove_box(&caller, lock_box_id, true)?;
 
+			self.release_tier_liquidity(lock_box.tier, lock_box.deposit, true);
 			self.total_liquidity -= &lock_box.deposit;
 			self.unclaimed_rewards -= &lock_box.interest;
 			self.claimed_rewards += &lock_box.interest;
 
-			self.transfer(caller.clone(), amount);
+			let (amount, reward) = self.pay(caller.clone(), lock_box.deposit, lock_box.interest);
 
 			self.env().emit_event(RedeemSuccessful {
 				staker: caller,
 				lock_box,
+				reward,
 			});
 
 			Ok(amount)
@@ -217,27 +535,82 @@ mod staking_rewards {
 		#[ink(message)]
 		pub fn early_withdraw(&mut self, lock_box_id: u128) -> Result<Balance, Error> {
 			let caller = self.env().caller();
-			self.ensure_not_self_account(&caller);
-
-			self.ensure_not_paused();
+			self.ensure_not_self_account(&caller)?;
 
-			let lock_box = self.remove_box(&caller, lock_box_id, false);
-			if lock_box.is_err() {
-				return Err(Error::LockBoxNotFound);
-			}
+			self.ensure_not_paused()?;
 
-			let lock_box = lock_box.unwrap();
+			let lock_box = self.remove_box(&caller, lock_box_id, false)?;
 
-			let amount = lock_box.deposit;
+			let interest = self.payable_interest(&lock_box, self.env().block_timestamp());
 
+			self.release_tier_liquidity(lock_box.tier, lock_box.deposit, true);
 			self.total_liquidity -= &lock_box.deposit;
 			self.unclaimed_rewards -= &lock_box.interest;
+			self.claimed_rewards += interest;
+			self.release_interest(lock_box.interest - interest);
 
-			self.transfer(caller.clone(), amount);
+			let (amount, reward) = self.pay(caller.clone(), lock_box.deposit, interest);
 
 			self.env().emit_event(WithdrawSuccessful {
 				staker: caller,
 				lock_box,
+				reward,
+			});
+
+			Ok(amount)
+		}
+
+		#[ink(message)]
+		pub fn partial_withdraw(
+			&mut self,
+			lock_box_id: u128,
+			principal: Balance,
+		) -> Result<Balance, Error> {
+			let caller = self.env().caller();
+			self.ensure_not_self_account(&caller)?;
+
+			self.ensure_not_paused()?;
+
+			let mut lock_box = self.get_user_box(&caller, lock_box_id)?;
+			if principal == 0 || principal > lock_box.deposit {
+				return Err(Error::InvalidAmount);
+			}
+
+			let now = self.env().block_timestamp();
+			if principal == lock_box.deposit {
+				return if now < lock_box.release {
+					self.early_withdraw(lock_box_id)
+				} else {
+					self.redeem(lock_box_id)
+				};
+			}
+
+			// Withdrawn part of the principal takes the same part of the interest
+			let promised = mul_div(lock_box.interest, principal, lock_box.deposit);
+			let interest = mul_div(
+				self.payable_interest(&lock_box, now),
+				principal,
+				lock_box.deposit,
+			);
+
+			lock_box.deposit -= principal;
+			lock_box.interest -= promised;
+			self.lock_boxes.insert(lock_box.id, &lock_box);
+
+			self.release_tier_liquidity(lock_box.tier, principal, false);
+			self.total_liquidity -= principal;
+			self.unclaimed_rewards -= promised;
+			self.claimed_rewards += interest;
+			self.release_interest(promised - interest);
+
+			let (amount, reward) = self.pay(caller.clone(), principal, interest);
+
+			self.env().emit_event(PartialWithdrawSuccessful {
+				staker: caller,
+				lock_box,
+				principal,
+				interest,
+				reward,
 			});
 
 			Ok(amount)
@@ -246,11 +619,52 @@ mod staking_rewards {
 		#[ink(message)]
 		pub fn refund(&mut self, amount: u128) -> Result<(), Error> {
 			let caller = Self::env().caller();
-			self.ensure_owner(&caller);
+			self.ensure_role(Role::Treasurer, &caller)?;
+
+			// Principal and promised native interest are never refundable
+			let locked = match self.reward_token {
+				Some(_) => self.total_liquidity,
+				None => self.total_liquidity + self.unclaimed_rewards,
+			};
+			let free_balance = self.env().balance().saturating_sub(locked);
+			if amount > free_balance {
+				return Err(Error::InsufficientContractBalance);
+			}
+
+			// Tokens sent outside `fund_rewards` are refunded before the reserve
+			let old_reward_reserve = self.reward_reserve;
+			let surplus = free_balance.saturating_sub(self.reward_reserve);
+			self.reward_reserve = self
+				.reward_reserve
+				.saturating_sub(amount.saturating_sub(surplus));
+
+			self.transfer_native(caller, amount);
+
+			self.env().emit_event(Refunded {
+				account: caller,
+				amount,
+				old_reward_reserve,
+				new_reward_reserve: self.reward_reserve,
+			});
+
+			Ok(())
+		}
+
+		#[ink(message, payable)]
+		pub fn fund_rewards(&mut self) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			self.ensure_role(Role::Treasurer, &caller)?;
 
-			assert!(amount <= self.env().balance(), "insufficient funds!");
+			let amount = self.env().transferred_value();
+			let old_reward_reserve = self.reward_reserve;
+			self.reward_reserve += amount;
 
-			self.transfer(caller, amount);
+			self.env().emit_event(RewardsFunded {
+				account: caller,
+				amount,
+				old_reward_reserve,
+				new_reward_reserve: self.reward_reserve,
+			});
 
 			Ok(())
 		}
@@ -260,15 +674,17 @@ mod staking_rewards {
 			&mut self,
 			max_deposit_value_opt: Option<u128>,
 			max_total_liquidity_opt: Option<u128>,
-			locking_duration_opt: Option<u64>,
 			deposit_deadline_opt: Option<u64>,
-			base_interest_percent_permil_opt: Option<u128>,
 			stakers_sample_opt: Option<u128>,
 			liquidity_sample_opt: Option<u128>,
 			negative_interest_multiplier_permil_opt: Option<u128>,
-		) {
+			interest_curve_opt: Option<InterestCurve>,
+			early_withdraw_penalty_percent_permil_opt: Option<u128>,
+		) -> Result<(), Error> {
 			let caller = Self::env().caller();
-			self.ensure_owner(&caller);
+			self.ensure_role(Role::ConfigAdmin, &caller)?;
+
+			let old = self.config();
 
 			if let Some(max_deposit_value) = max_deposit_value_opt {
 				self.max_deposit_value = max_deposit_value;
@@ -278,18 +694,10 @@ mod staking_rewards {
 				self.max_total_liquidity = max_total_liquidity;
 			}
 
-			if let Some(locking_duration) = locking_duration_opt {
-				self.locking_duration = locking_duration;
-			}
-
 			if let Some(deposit_deadline) = deposit_deadline_opt {
 				self.deposit_deadline = deposit_deadline;
 			}
 
-			if let Some(base_interest_percent_permil) = base_interest_percent_permil_opt {
-				self.base_interest_percent_permil = base_interest_percent_permil;
-			}
-
 			if let Some(stakers_sample) = stakers_sample_opt {
 				self.stakers_sample = stakers_sample;
 			}
@@ -303,137 +711,412 @@ mod staking_rewards {
 			{
 				self.negative_interest_multiplier_permil = negative_interest_multiplier_permil;
 			}
+
+			if let Some(interest_curve) = interest_curve_opt {
+				self.interest_curve = interest_curve;
+			}
+
+			if let Some(early_withdraw_penalty_percent_permil) =
+				early_withdraw_penalty_percent_permil_opt
+			{
+				self.early_withdraw_penalty_percent_permil =
+					early_withdraw_penalty_percent_permil.min(100 * MIL);
+			}
+
+			self.env().emit_event(ContractConfigured {
+				old,
+				new: self.config(),
+			});
+
+			Ok(())
 		}
 
 		#[ink(message)]
-		pub fn set_box(&mut self, lock_box: LockBox) {
+		pub fn add_tier(
+			&mut self,
+			locking_duration: u64,
+			base_interest_percent_permil: u128,
+			capacity: u128,
+		) -> Result<u32, Error> {
 			let caller = Self::env().caller();
-			self.ensure_owner(&caller);
-			self.lock_boxes.insert(lock_box.id, &lock_box);
+			self.ensure_role(Role::ConfigAdmin, &caller)?;
+
+			Ok(self.insert_tier(locking_duration, base_interest_percent_permil, capacity))
 		}
 
 		#[ink(message)]
-		pub fn pause_contract(&mut self) {
+		pub fn configure_tier(
+			&mut self,
+			tier_id: u32,
+			locking_duration_opt: Option<u64>,
+			base_interest_percent_permil_opt: Option<u128>,
+			capacity_opt: Option<u128>,
+		) -> Result<(), Error> {
 			let caller = Self::env().caller();
-			self.ensure_owner(&caller);
-			self.is_paused = true;
+			self.ensure_role(Role::ConfigAdmin, &caller)?;
+
+			let old = self.tiers.get(tier_id).ok_or(Error::TierNotFound)?;
+			let mut tier = old;
+
+			if let Some(locking_duration) = locking_duration_opt {
+				tier.locking_duration = locking_duration;
+			}
+
+			if let Some(base_interest_percent_permil) = base_interest_percent_permil_opt {
+				tier.base_interest_percent_permil = base_interest_percent_permil;
+			}
+
+			if let Some(capacity) = capacity_opt {
+				tier.capacity = capacity;
+			}
+
+			self.tiers.insert(tier_id, &tier);
+
+			self.env().emit_event(TierSet {
+				id: tier_id,
+				old: Some(old),
+				new: tier,
+			});
+
+			Ok(())
 		}
 
+		/// Moves the release of an existing lock box. Other fields are backed by
+		/// the liquidity and reserve accounting, so they can't be changed
 		#[ink(message)]
-		pub fn resume_contract(&mut self) {
+		pub fn set_box(&mut self, lock_box: LockBox) -> Result<(), Error> {
 			let caller = Self::env().caller();
-			self.ensure_owner(&caller);
-			self.is_paused = false;
+			self.ensure_role(Role::ConfigAdmin, &caller)?;
+
+			let old = self
+				.lock_boxes
+				.get(lock_box.id)
+				.ok_or(Error::LockBoxNotFound)?;
+			let release_only = LockBox {
+				release: lock_box.release,
+				..old
+			};
+			if lock_box != release_only {
+				return Err(Error::InvalidLockBoxChange);
+			}
+			self.lock_boxes.insert(lock_box.id, &lock_box);
+
+			self.env().emit_event(LockBoxSet { old, new: lock_box });
+			Ok(())
 		}
 
 		#[ink(message)]
-		pub fn set_code(&mut self, code_hash: [u8; 32]) {
+		pub fn pause_contract(&mut self) -> Result<(), Error> {
 			let caller = Self::env().caller();
-			self.ensure_owner(&caller);
-
-			ink_env::set_code_hash(&code_hash).unwrap_or_else(|err| {
-				panic!(
-					"Failed to `set_code_hash` to {:?} due to {:?}",
-					code_hash, err
-				)
-			});
-			ink_env::debug_println!("Switched code hash to {:?}.", code_hash);
+			self.ensure_role(Role::Pauser, &caller)?;
+			self.set_paused(caller, true);
+			Ok(())
 		}
 
 		#[ink(message)]
-		pub fn get_box_ids_for_account(&self, account_id: AccountId) -> Option<Vec<u128>> {
-			self.user_boxes.get(&account_id)
+		pub fn resume_contract(&mut self) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			self.ensure_role(Role::Pauser, &caller)?;
+			self.set_paused(caller, false);
+			Ok(())
 		}
 
+		/// Switches the code of the contract. The new code must keep the storage layout,
+		/// contracts deployed before tiers have to be replaced by a fresh deployment
 		#[ink(message)]
-		pub fn get_boxes_for_account(&self, account_id: AccountId) -> Option<Vec<LockBox>> {
-			let box_ids = self.user_boxes.get(&account_id);
+		pub fn set_code(&mut self, code_hash: [u8; 32]) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			self.ensure_role(Role::Upgrader, &caller)?;
 
-			match box_ids {
-				Some(vec) => Some(
-					vec.iter()
-						.map(|lock_box_id| self.lock_boxes.get(lock_box_id).unwrap())
-						.collect(),
-				),
-				None => None,
-			}
-		}
+			ink_env::set_code_hash(&code_hash).map_err(|_| Error::SetCodeHashFailed)?;
+			ink_env::debug_println!("Switched code hash to {:?}.", code_hash);
 
-		#[ink(message)]
-		pub fn get_total_staked_by_account(&self, account_id: AccountId) -> Option<u128> {
-			let box_ids = self.user_boxes.get(&account_id);
+			let old = self.code_hash.replace(code_hash);
+			self.env().emit_event(CodeUpgraded {
+				old,
+				new: code_hash,
+			});
 
-			match box_ids {
-				Some(vec) => Some(
-					vec.iter()
-						.map(|lock_box_id| self.lock_boxes.get(lock_box_id).unwrap().deposit)
-						.sum(),
-				),
-				None => None,
-			}
+			Ok(())
 		}
 
 		#[ink(message)]
-		pub fn get_box(&self, lock_box_id: u128) -> Option<LockBox> {
-			self.lock_boxes.get(lock_box_id)
+		pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			self.ensure_owner(&caller)?;
+
+			self.pending_owner = Some(new_owner);
+
+			self.env().emit_event(OwnershipTransferStarted {
+				owner: caller,
+				pending_owner: new_owner,
+			});
+
+			Ok(())
 		}
 
 		#[ink(message)]
-		pub fn get_metadata(&self) -> Metadata {
-			Metadata {
-				owner: self.owner,
+		pub fn accept_ownership(&mut self) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			if self.pending_owner != Some(caller) {
+				return Err(Error::NotPendingOwner);
+			}
+
+			let previous_owner = self.owner;
+			self.owner = caller;
+			self.pending_owner = None;
+
+			self.env().emit_event(OwnershipTransferred {
+				previous_owner,
+				new_owner: caller,
+			});
+
+			// Roles held by the previous owner are handed over with the ownership
+			for role in ROLES {
+				if self.has_role(role, previous_owner) {
+					self.set_role(role, previous_owner, false);
+					self.set_role(role, caller, true);
+				}
+			}
+
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			self.ensure_owner(&caller)?;
+
+			self.set_role(role, account, true);
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<(), Error> {
+			let caller = Self::env().caller();
+			self.ensure_owner(&caller)?;
+
+			self.set_role(role, account, false);
+			Ok(())
+		}
+
+		#[ink(message)]
+		pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+			self.roles.get((role, account)).unwrap_or(false)
+		}
+
+		#[ink(message)]
+		pub fn get_box_ids_for_account(&self, account_id: AccountId) -> Option<Vec<u128>> {
+			self.user_boxes.get(&account_id)
+		}
+
+		#[ink(message)]
+		pub fn get_boxes_for_account(&self, account_id: AccountId) -> Option<Vec<LockBox>> {
+			let box_ids = self.user_boxes.get(&account_id);
+
+			match box_ids {
+				Some(vec) => Some(
+					vec.iter()
+						.map(|lock_box_id| self.lock_boxes.get(lock_box_id).unwrap())
+						.collect(),
+				),
+				None => None,
+			}
+		}
+
+		#[ink(message)]
+		pub fn get_total_staked_by_account(&self, account_id: AccountId) -> Option<u128> {
+			let box_ids = self.user_boxes.get(&account_id);
+
+			match box_ids {
+				Some(vec) => Some(
+					vec.iter()
+						.map(|lock_box_id| self.lock_boxes.get(lock_box_id).unwrap().deposit)
+						.sum(),
+				),
+				None => None,
+			}
+		}
+
+		#[ink(message)]
+		pub fn get_box(&self, lock_box_id: u128) -> Option<LockBox> {
+			self.lock_boxes.get(lock_box_id)
+		}
+
+		#[ink(message)]
+		pub fn get_accrued_interest(&self, lock_box_id: u128) -> Option<Balance> {
+			let lock_box = self.lock_boxes.get(lock_box_id)?;
+			Some(self.accrued_interest(&lock_box, self.env().block_timestamp()))
+		}
+
+		#[ink(message)]
+		pub fn get_tier(&self, tier_id: u32) -> Option<Tier> {
+			self.tiers.get(tier_id)
+		}
+
+		#[ink(message)]
+		pub fn get_metadata(&self) -> Metadata {
+			Metadata {
+				owner: self.owner,
+				pending_owner: self.pending_owner,
 				max_deposit_value: self.max_deposit_value,
 				min_deposit_value: self.min_deposit_value,
 				max_total_liquidity: self.max_total_liquidity,
 				max_stakers: self.max_stakers,
-				locking_duration: self.locking_duration,
 				deposit_deadline: self.deposit_deadline,
-				base_interest_percent_permil: self.base_interest_percent_permil,
 				stakers_sample: self.stakers_sample,
 				liquidity_sample: self.liquidity_sample,
 				negative_interest_multiplier_permil: self.negative_interest_multiplier_permil,
+				interest_curve: self.interest_curve,
+				early_withdraw_penalty_percent_permil: self.early_withdraw_penalty_percent_permil,
 				total_liquidity: self.total_liquidity,
 				claimed_rewards: self.claimed_rewards,
 				unclaimed_rewards: self.unclaimed_rewards,
+				reward_reserve: self.reward_reserve,
+				reward_token: self.reward_token,
+				reward_rate_permil: self.reward_rate_permil,
+				reserved_reward_tokens: self.reserved_reward_tokens,
 				stakers_count: self.stakers_count,
-				dynamic_interest_percent_permil: self.interest_percent_permil(),
+				tiers: (0..self.tiers_count)
+					.filter_map(|id| {
+						self.tiers.get(id).map(|tier| TierMetadata {
+							id,
+							tier,
+							dynamic_interest_percent_permil: self.interest_percent_permil(&tier),
+						})
+					})
+					.collect(),
 				is_paused: self.is_paused,
 			}
 		}
 
-		fn interest_percent_permil(&self) -> u128 {
-			let negative_interest_percent_permil = self.negative_interest_multiplier_permil
-				* self.log2_permil(
-					1 + self.stakers_count / self.stakers_sample
-						+ self.total_liquidity / self.liquidity_sample,
-				) / MIL;
-
-			if self.base_interest_percent_permil >= negative_interest_percent_permil {
-				self.base_interest_percent_permil - negative_interest_percent_permil
-			} else {
-				0
+		fn config(&self) -> ContractConfig {
+			ContractConfig {
+				max_deposit_value: self.max_deposit_value,
+				max_total_liquidity: self.max_total_liquidity,
+				deposit_deadline: self.deposit_deadline,
+				stakers_sample: self.stakers_sample,
+				liquidity_sample: self.liquidity_sample,
+				negative_interest_multiplier_permil: self.negative_interest_multiplier_permil,
+				interest_curve: self.interest_curve,
+				early_withdraw_penalty_percent_permil: self.early_withdraw_penalty_percent_permil,
 			}
 		}
 
-		fn add_box(&mut self, account: &AccountId, lock_box: &LockBox) -> Result<(), Error> {
-			let boxes = self.user_boxes.get(&account);
+		fn set_paused(&mut self, account: AccountId, paused: bool) {
+			let old = self.is_paused;
+			self.is_paused = paused;
 
-			match boxes {
-				Some(mut boxes) => {
-					boxes.push(lock_box.id);
-					self.user_boxes.insert(&account, &boxes);
-				}
-				None => {
-					if self.stakers_count == self.max_stakers {
-						return Err(Error::TooManyStakers);
-					}
+			self.env().emit_event(PauseChanged {
+				account,
+				old,
+				new: paused,
+			});
+		}
 
-					self.stakers_count += 1;
-					self.user_boxes.insert(&account, &vec![lock_box.id]);
+		fn insert_tier(
+			&mut self,
+			locking_duration: u64,
+			base_interest_percent_permil: u128,
+			capacity: u128,
+		) -> u32 {
+			let tier_id = self.tiers_count;
+			let tier = Tier {
+				locking_duration,
+				base_interest_percent_permil,
+				capacity,
+				total_liquidity: 0,
+				lock_boxes_count: 0,
+			};
+			self.tiers.insert(tier_id, &tier);
+			self.tiers_count += 1;
+
+			self.env().emit_event(TierSet {
+				id: tier_id,
+				old: None,
+				new: tier,
+			});
+
+			tier_id
+		}
+
+		fn release_tier_liquidity(&mut self, tier_id: u32, amount: Balance, box_closed: bool) {
+			if let Some(mut tier) = self.tiers.get(tier_id) {
+				tier.total_liquidity = tier.total_liquidity.saturating_sub(amount);
+				if box_closed {
+					tier.lock_boxes_count = tier.lock_boxes_count.saturating_sub(1);
 				}
+				self.tiers.insert(tier_id, &tier);
+			}
+		}
+
+		/// Interest accrued linearly from `created_at` until `release`
+		fn accrued_interest(&self, lock_box: &LockBox, now: Timestamp) -> Balance {
+			if now >= lock_box.release || lock_box.release <= lock_box.created_at {
+				return lock_box.interest;
+			}
+
+			let elapsed = now.saturating_sub(lock_box.created_at);
+			let duration = lock_box.release - lock_box.created_at;
+			mul_div(lock_box.interest, elapsed as u128, duration as u128)
+		}
+
+		/// Interest paid when withdrawing now, the early withdraw penalty
+		/// is taken from accrued interest before release
+		fn payable_interest(&self, lock_box: &LockBox, now: Timestamp) -> Balance {
+			let accrued = self.accrued_interest(lock_box, now);
+			if now >= lock_box.release {
+				return accrued;
+			}
+
+			let penalty = mul_div(
+				accrued,
+				self.early_withdraw_penalty_percent_permil,
+				100 * MIL,
+			);
+			accrued.saturating_sub(penalty)
+		}
+
+		fn get_user_box(&self, account: &AccountId, lock_box_id: u128) -> Result<LockBox, Error> {
+			let owns_box = self
+				.user_boxes
+				.get(account)
+				.map_or(false, |box_ids| box_ids.contains(&lock_box_id));
+			if !owns_box {
+				return Err(Error::LockBoxNotFound);
+			}
+
+			self.lock_boxes
+				.get(lock_box_id)
+				.ok_or(Error::LockBoxNotFound)
+		}
+
+		fn interest_percent_permil(&self, tier: &Tier) -> u128 {
+			let samples = (self.stakers_count / self.stakers_sample)
+				.saturating_add(self.total_liquidity / self.liquidity_sample);
+			let negative_interest_percent_permil = mul_div(
+				self.negative_interest_multiplier_permil,
+				self.interest_curve.apply_permil(samples),
+				MIL,
+			);
+
+			if tier.base_interest_percent_permil >= negative_interest_percent_permil {
+				tier.base_interest_percent_permil - negative_interest_percent_permil
+			} else {
+				0
 			}
+		}
 
+		fn add_box(&mut self, account: &AccountId, lock_box: &LockBox) -> Result<(), Error> {
+			self.attach_box(account, lock_box.id)?;
 			self.lock_boxes.insert(lock_box.id, lock_box);
+
+			self.env().emit_event(Transfer {
+				from: None,
+				to: Some(*account),
+				id: Id::U128(lock_box.id),
+			});
+
 			Ok(())
 		}
 
@@ -443,220 +1126,443 @@ mod staking_rewards {
 			lock_box_id: u128,
 			should_check_release: bool,
 		) -> Result<LockBox, Error> {
-			let user_box_ids = self.user_boxes.get(&account);
+			let lock_box = self.get_user_box(account, lock_box_id)?;
 
-			if user_box_ids.is_none() {
-				return Err(Error::LockBoxNotFound);
+			if should_check_release {
+				let now = self.env().block_timestamp();
+				if now < lock_box.release {
+					return Err(Error::LockBoxNotReleased);
+				}
 			}
 
-			let mut user_box_ids = user_box_ids.unwrap();
+			self.detach_box(account, lock_box_id)?;
+			self.lock_boxes.remove(lock_box_id);
 
-			let box_index = user_box_ids
-				.iter()
-				.position(|&element| element == lock_box_id);
-			if box_index.is_none() {
-				return Err(Error::LockBoxNotFound);
-			}
+			self.env().emit_event(Transfer {
+				from: Some(*account),
+				to: None,
+				id: Id::U128(lock_box_id),
+			});
 
-			let box_index = box_index.unwrap();
+			Ok(lock_box)
+		}
 
-			if should_check_release {
-				let now = self.env().block_timestamp();
+		/// Gives the ownership of a lock box to `account`
+		fn attach_box(&mut self, account: &AccountId, lock_box_id: u128) -> Result<(), Error> {
+			match self.user_boxes.get(account) {
+				Some(mut boxes) => {
+					boxes.push(lock_box_id);
+					self.user_boxes.insert(account, &boxes);
+				}
+				None => {
+					if self.stakers_count == self.max_stakers {
+						return Err(Error::TooManyStakers);
+					}
 
-				let lock_box = self.lock_boxes.get(lock_box_id).unwrap();
-				if now < lock_box.release {
-					return Err(Error::LockBoxNotReleased);
+					self.stakers_count += 1;
+					self.user_boxes.insert(account, &vec![lock_box_id]);
 				}
 			}
 
+			self.box_owners.insert(lock_box_id, account);
+			Ok(())
+		}
+
+		/// Takes the ownership of a lock box from `account`, clearing its approval
+		fn detach_box(&mut self, account: &AccountId, lock_box_id: u128) -> Result<(), Error> {
+			let mut user_box_ids = self.user_boxes.get(account).ok_or(Error::LockBoxNotFound)?;
+			let box_index = user_box_ids
+				.iter()
+				.position(|&element| element == lock_box_id)
+				.ok_or(Error::LockBoxNotFound)?;
+
 			if user_box_ids.len() != 1 {
 				user_box_ids.swap_remove(box_index);
-				self.user_boxes.insert(&account, &user_box_ids);
+				self.user_boxes.insert(account, &user_box_ids);
 			} else {
 				self.stakers_count -= 1;
-				self.user_boxes.remove(&account);
+				self.user_boxes.remove(account);
 			};
 
-			let lock_box = self.lock_boxes.get(lock_box_id).unwrap();
-			self.lock_boxes.remove(lock_box_id);
-
-			Ok(lock_box)
+			self.box_owners.remove(lock_box_id);
+			self.box_approvals.remove(lock_box_id);
+			Ok(())
 		}
 
-		fn transfer(&mut self, account: AccountId, amount: u128) {
-			if self.env().transfer(account, amount).is_err() {
-				panic!("Could not perform transfer");
+		/// Sets aside the interest promised to a new lock box, from the reward reserve or
+		/// from the reward token balance of the contract not yet reserved for other boxes
+		fn reserve_interest(&mut self, interest: Balance) -> Result<(), Error> {
+			match self.reward_token {
+				Some(token) => {
+					let reserved = self.reserved_reward_tokens + self.reward_amount(interest);
+					if reserved > psp22::balance_of(token, self.env().account_id()) {
+						return Err(Error::InsufficientRewardReserve);
+					}
+					self.reserved_reward_tokens = reserved;
+				}
+				None => {
+					if interest > self.reward_reserve {
+						return Err(Error::InsufficientRewardReserve);
+					}
+					self.reward_reserve -= interest;
+				}
 			}
-		}
 
-		fn ensure_not_self_account(&mut self, account: &AccountId) {
-			assert_ne!(
-				account,
-				&self.env().account_id(),
-				"account should not be the own smart contract"
-			);
+			Ok(())
 		}
 
-		fn ensure_owner(&mut self, account: &AccountId) {
-			assert_eq!(account, &self.owner, "account is not owner");
-		}
-
-		fn ensure_not_paused(&self) {
-			assert!(!self.is_paused, "contract is paused");
-		}
-
-		fn log2_permil(&self, num: u128) -> u128 {
-			match num {
-				1 => 0,
-				2 => 1000000,
-				3 => 1584963,
-				4 => 2000000,
-				5 => 2321928,
-				6 => 2584963,
-				7 => 2807355,
-				8 => 3000000,
-				9 => 3169925,
-				10 => 3321928,
-				11 => 3459432,
-				12 => 3584963,
-				13 => 3700440,
-				14 => 3807355,
-				15 => 3906891,
-				16 => 4000000,
-				17 => 4087463,
-				18 => 4169925,
-				19 => 4247928,
-				20 => 4321928,
-				21 => 4392317,
-				22 => 4459432,
-				23 => 4584963,
-				24 => 4643856,
-				25 => 4700440,
-				26 => 4754888,
-				27 => 4807355,
-				28 => 4857981,
-				30 => 4906891,
-				31 => 4954196,
-				32 => 5000000,
-				33 => 5044394,
-				34 => 5087463,
-				35 => 5129283,
-				36 => 5169925,
-				37 => 5209453,
-				38 => 5247928,
-				39 => 5285402,
-				40 => 5321928,
-				41 => 5357552,
-				42 => 5392317,
-				43 => 5426265,
-				44 => 5459432,
-				45 => 5491853,
-				46 => 5523562,
-				47 => 5554589,
-				48 => 5584963,
-				49 => 5614710,
-				50 => 5643856,
-				51 => 5672425,
-				52 => 5700440,
-				53 => 5727920,
-				54 => 5754888,
-				55 => 5781360,
-				56 => 5807355,
-				57 => 5832890,
-				58 => 5857981,
-				59 => 5882643,
-				60 => 5906891,
-				61 => 5930737,
-				62 => 5954196,
-				63 => 5977280,
-				64 => 6000000,
-				_ => {
-					panic!("Could not perform log2");
-				}
+		/// Returns forfeited interest to the reward reserve, or its reward tokens
+		/// to the unreserved token balance
+		fn release_interest(&mut self, interest: Balance) {
+			match self.reward_token {
+				Some(_) => self.unreserve_reward_tokens(self.reward_amount(interest)),
+				None => self.reward_reserve += interest,
 			}
 		}
-	}
 
-	#[cfg(test)]
-	mod tests {
-		use super::*;
+		fn unreserve_reward_tokens(&mut self, reward: Balance) {
+			self.reserved_reward_tokens = self.reserved_reward_tokens.saturating_sub(reward);
+		}
 
-		use ink_env::{test, AccountId, DefaultEnvironment};
+		fn reward_amount(&self, interest: Balance) -> Balance {
+			mul_div(interest, self.reward_rate_permil, MIL)
+		}
 
-		const MAX_DEPOSIT_VALUE: u128 = u128::MAX / MIL / 100;
-		const INITIAL_BALANCE: u128 = 5;
-		const MAX_STAKERS: u128 = 10_000;
+		/// Pays back the principal in native tokens and the interest in the reward token, if any.
+		/// Returns the paid native amount and reward tokens. Reverts the message when a transfer fails
+		fn pay(
+			&mut self,
+			account: AccountId,
+			principal: Balance,
+			interest: Balance,
+		) -> (Balance, Balance) {
+			let token = match self.reward_token {
+				Some(token) => token,
+				None => {
+					self.transfer_native(account, principal + interest);
+					return (principal + interest, 0);
+				}
+			};
 
-		#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
-		#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-		pub enum TestError {
-			AccountNotFound,
-		}
+			let reward = self.reward_amount(interest);
+			self.unreserve_reward_tokens(reward);
+			if reward > 0 && psp22::transfer(token, account, reward).is_err() {
+				revert(Error::RewardTransferFailed);
+			}
 
-		fn default_accounts() -> test::DefaultAccounts<DefaultEnvironment> {
-			test::default_accounts::<DefaultEnvironment>()
+			self.transfer_native(account, principal);
+			(principal, reward)
 		}
 
-		fn set_caller(acc_id: AccountId) {
-			test::set_caller::<DefaultEnvironment>(acc_id);
+		/// Transfers are the last step of messages, after storage has been written.
+		/// ink! commits that storage even when a message returns `Err`,
+		/// so a failed transfer reverts the whole message instead
+		fn transfer_native(&mut self, account: AccountId, amount: u128) {
+			if self.env().transfer(account, amount).is_err() {
+				revert(Error::TransferFailed);
+			}
 		}
 
-		fn set_callee(acc_id: AccountId) {
-			test::set_callee::<DefaultEnvironment>(acc_id);
+		fn ensure_not_self_account(&self, account: &AccountId) -> Result<(), Error> {
+			if account == &self.env().account_id() {
+				return Err(Error::SelfCall);
+			}
+			Ok(())
 		}
 
-		fn set_value_transferred(value: u128) {
-			test::set_value_transferred::<DefaultEnvironment>(value);
+		fn ensure_owner(&self, account: &AccountId) -> Result<(), Error> {
+			if account != &self.owner {
+				return Err(Error::NotOwner);
+			}
+			Ok(())
 		}
 
-		fn set_account_balance(account_id: AccountId, value: u128) {
-			test::set_account_balance::<DefaultEnvironment>(account_id, value);
+		fn ensure_role(&self, role: Role, account: &AccountId) -> Result<(), Error> {
+			if !self.has_role(role, *account) {
+				return Err(Error::MissingRole);
+			}
+			Ok(())
 		}
 
-		fn get_account_balance(account_id: AccountId) -> Result<u128, TestError> {
-			test::get_account_balance::<DefaultEnvironment>(account_id)
-				.map_err(|_| TestError::AccountNotFound)
-		}
+		fn set_role(&mut self, role: Role, account: AccountId, granted: bool) {
+			if self.has_role(role, account) == granted {
+				return;
+			}
 
-		fn alice_id() -> AccountId {
-			default_accounts().alice
+			if granted {
+				self.roles.insert((role, account), &true);
+				self.env().emit_event(RoleGranted { role, account });
+			} else {
+				self.roles.remove((role, account));
+				self.env().emit_event(RoleRevoked { role, account });
+			}
 		}
 
-		fn bob_id() -> AccountId {
-			default_accounts().bob
+		fn ensure_not_paused(&self) -> Result<(), Error> {
+			if self.is_paused {
+				return Err(Error::Paused);
+			}
+			Ok(())
 		}
+	}
 
-		fn charlie_id() -> AccountId {
-			default_accounts().charlie
+	impl From<Error> for PSP34Error {
+		fn from(error: Error) -> Self {
+			PSP34Error::Custom(format!("{:?}", error))
 		}
+	}
 
-		fn contract_id() -> AccountId {
-			charlie_id()
+	/// Lock box ids are the PSP34 token ids
+	fn lock_box_id(id: &Id) -> Option<u128> {
+		match id {
+			Id::U8(id) => Some(*id as u128),
+			Id::U16(id) => Some(*id as u128),
+			Id::U32(id) => Some(*id as u128),
+			Id::U64(id) => Some(*id as u128),
+			Id::U128(id) => Some(*id),
+			Id::Bytes(_) => None,
 		}
+	}
 
-		fn owner_id() -> AccountId {
-			alice_id()
+	impl PSP34 for StakingRewards {
+		#[ink(message)]
+		fn collection_id(&self) -> Id {
+			Id::Bytes(scale::Encode::encode(&self.env().account_id()))
 		}
 
-		fn advance_block() {
-			test::advance_block::<DefaultEnvironment>();
+		#[ink(message)]
+		fn balance_of(&self, owner: AccountId) -> u32 {
+			self.user_boxes
+				.get(&owner)
+				.map_or(0, |box_ids| box_ids.len() as u32)
 		}
 
-		fn build_contract() -> StakingRewards {
-			set_caller(owner_id());
-			set_account_balance(contract_id(), INITIAL_BALANCE);
-			StakingRewards::new(
-				MAX_DEPOSIT_VALUE,
-				1,
-				MAX_DEPOSIT_VALUE,
-				MAX_STAKERS,
-				6,
-				12,
-				5_000_000,
-				1,
-				1,
-				0,
-			)
+		#[ink(message)]
+		fn owner_of(&self, id: Id) -> Option<AccountId> {
+			self.box_owners.get(lock_box_id(&id)?)
+		}
+
+		#[ink(message)]
+		fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool {
+			if self
+				.operator_approvals
+				.get((owner, operator))
+				.unwrap_or(false)
+			{
+				return true;
+			}
+
+			id.as_ref()
+				.and_then(lock_box_id)
+				.filter(|lock_box_id| self.box_owners.get(lock_box_id) == Some(owner))
+				.map_or(false, |lock_box_id| {
+					self.box_approvals.get(lock_box_id) == Some(operator)
+				})
+		}
+
+		#[ink(message)]
+		fn approve(
+			&mut self,
+			operator: AccountId,
+			id: Option<Id>,
+			approved: bool,
+		) -> Result<(), PSP34Error> {
+			let caller = self.env().caller();
+			if operator == caller {
+				return Err(PSP34Error::SelfApprove);
+			}
+
+			match &id {
+				Some(id) => {
+					let lock_box_id = lock_box_id(id).ok_or(PSP34Error::TokenNotExists)?;
+					let owner = self
+						.box_owners
+						.get(lock_box_id)
+						.ok_or(PSP34Error::TokenNotExists)?;
+					if owner != caller {
+						return Err(PSP34Error::NotApproved);
+					}
+
+					if approved {
+						self.box_approvals.insert(lock_box_id, &operator);
+					} else {
+						self.box_approvals.remove(lock_box_id);
+					}
+				}
+				None => {
+					if approved {
+						self.operator_approvals.insert((caller, operator), &true);
+					} else {
+						self.operator_approvals.remove((caller, operator));
+					}
+				}
+			}
+
+			self.env().emit_event(Approval {
+				owner: caller,
+				operator,
+				id,
+				approved,
+			});
+
+			Ok(())
+		}
+
+		#[ink(message)]
+		fn transfer(&mut self, to: AccountId, id: Id, _data: Vec<u8>) -> Result<(), PSP34Error> {
+			let caller = self.env().caller();
+			self.ensure_not_paused()?;
+			self.ensure_not_self_account(&to)?;
+
+			let lock_box_id = lock_box_id(&id).ok_or(PSP34Error::TokenNotExists)?;
+			let from = self
+				.box_owners
+				.get(lock_box_id)
+				.ok_or(PSP34Error::TokenNotExists)?;
+			if caller != from && !self.allowance(from, caller, Some(id.clone())) {
+				return Err(PSP34Error::NotApproved);
+			}
+
+			// Storage is committed even when the message fails, so the new staker
+			// must fit before the box is taken from its current owner
+			let adds_staker = to != from && self.user_boxes.get(&to).is_none();
+			let removes_staker = self
+				.user_boxes
+				.get(&from)
+				.map_or(false, |box_ids| box_ids.len() == 1);
+			if adds_staker && !removes_staker && self.stakers_count >= self.max_stakers {
+				return Err(Error::TooManyStakers.into());
+			}
+
+			self.detach_box(&from, lock_box_id)?;
+			self.attach_box(&to, lock_box_id)?;
+
+			self.env().emit_event(Transfer {
+				from: Some(from),
+				to: Some(to),
+				id,
+			});
+
+			Ok(())
+		}
+
+		#[ink(message)]
+		fn total_supply(&self) -> Balance {
+			(0..self.tiers_count)
+				.filter_map(|id| self.tiers.get(id))
+				.map(|tier| tier.lock_boxes_count)
+				.sum()
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+
+		use ink_env::{test, AccountId, DefaultEnvironment};
+
+		const MAX_DEPOSIT_VALUE: u128 = u128::MAX / MIL / 100;
+		const INITIAL_BALANCE: u128 = 5;
+		const INITIAL_RESERVE: u128 = MAX_DEPOSIT_VALUE;
+		const MAX_STAKERS: u128 = 10_000;
+
+		#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq, Copy, Clone)]
+		#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+		pub enum TestError {
+			AccountNotFound,
+		}
+
+		fn default_accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+			test::default_accounts::<DefaultEnvironment>()
+		}
+
+		fn set_caller(acc_id: AccountId) {
+			test::set_caller::<DefaultEnvironment>(acc_id);
+		}
+
+		fn set_callee(acc_id: AccountId) {
+			test::set_callee::<DefaultEnvironment>(acc_id);
+		}
+
+		fn set_value_transferred(value: u128) {
+			test::set_value_transferred::<DefaultEnvironment>(value);
+		}
+
+		fn set_account_balance(account_id: AccountId, value: u128) {
+			test::set_account_balance::<DefaultEnvironment>(account_id, value);
+		}
+
+		fn get_account_balance(account_id: AccountId) -> Result<u128, TestError> {
+			test::get_account_balance::<DefaultEnvironment>(account_id)
+				.map_err(|_| TestError::AccountNotFound)
+		}
+
+		fn alice_id() -> AccountId {
+			default_accounts().alice
+		}
+
+		fn bob_id() -> AccountId {
+			default_accounts().bob
+		}
+
+		fn charlie_id() -> AccountId {
+			default_accounts().charlie
+		}
+
+		fn contract_id() -> AccountId {
+			charlie_id()
+		}
+
+		fn owner_id() -> AccountId {
+			alice_id()
+		}
+
+		fn advance_block() {
+			test::advance_block::<DefaultEnvironment>();
+		}
+
+		fn build_contract() -> StakingRewards {
+			set_caller(owner_id());
+			set_account_balance(contract_id(), INITIAL_BALANCE);
+			set_value_transferred(INITIAL_RESERVE);
+			StakingRewards::new(
+				MAX_DEPOSIT_VALUE,
+				1,
+				MAX_DEPOSIT_VALUE,
+				MAX_STAKERS,
+				6,
+				12,
+				5_000_000,
+				1,
+				1,
+				0,
+				None,
+				0,
+			)
+		}
+
+		fn token_id() -> AccountId {
+			default_accounts().frank
+		}
+
+		fn build_token_contract(reward_rate_permil: u128) -> StakingRewards {
+			set_caller(owner_id());
+			set_account_balance(contract_id(), INITIAL_BALANCE);
+			set_value_transferred(INITIAL_RESERVE);
+			StakingRewards::new(
+				MAX_DEPOSIT_VALUE,
+				1,
+				MAX_DEPOSIT_VALUE,
+				MAX_STAKERS,
+				6,
+				12,
+				5_000_000,
+				1,
+				1,
+				0,
+				Some(token_id()),
+				reward_rate_permil,
+			)
 		}
 
 		#[test]
@@ -667,7 +1573,7 @@ mod staking_rewards {
 			set_callee(contract_id());
 			set_value_transferred(100);
 
-			assert!(sc.deposit().is_ok());
+			assert!(sc.deposit(0).is_ok());
 
 			advance_block();
 			advance_block();
@@ -697,7 +1603,7 @@ mod staking_rewards {
 			set_caller(bob_id());
 			set_callee(contract_id());
 			set_value_transferred(MAX_DEPOSIT_VALUE);
-			assert!(sc.deposit().is_ok());
+			assert!(sc.deposit(0).is_ok());
 		}
 
 		#[test]
@@ -707,7 +1613,702 @@ mod staking_rewards {
 			set_caller(bob_id());
 			set_callee(contract_id());
 			set_value_transferred(MAX_DEPOSIT_VALUE + 1);
-			assert_eq!(sc.deposit(), Err(Error::DepositTooBig));
+			assert_eq!(sc.deposit(0), Err(Error::DepositTooBig));
+		}
+
+		#[test]
+		fn test_errors_are_returned_instead_of_panics() {
+			let mut sc = build_contract();
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			assert_eq!(sc.pause_contract(), Err(Error::MissingRole));
+			assert_eq!(sc.add_tier(18, 10_000_000, 150), Err(Error::MissingRole));
+
+			set_caller(contract_id());
+			set_value_transferred(100);
+			assert_eq!(sc.deposit(0), Err(Error::SelfCall));
+
+			set_caller(owner_id());
+			assert_eq!(
+				sc.refund(INITIAL_BALANCE + 1),
+				Err(Error::InsufficientContractBalance)
+			);
+			assert_eq!(sc.pause_contract(), Ok(()));
+
+			set_caller(bob_id());
+			assert_eq!(sc.deposit(0), Err(Error::Paused));
+		}
+
+		#[test]
+		#[should_panic(expected = "seal_return_value")]
+		fn test_failed_payout_reverts() {
+			let mut sc = build_contract();
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(0).unwrap();
+			assert_eq!(sc.get_box(lock_box.id), Some(lock_box));
+			assert_eq!(sc.get_total_staked_by_account(bob_id()), Some(100));
+
+			// Contract cannot pay the box back, the message reverts instead of returning `Err`
+			// with the box already removed
+			set_account_balance(contract_id(), 0);
+			let _ = sc.early_withdraw(lock_box.id);
+		}
+
+		#[test]
+		fn test_deposit_above_max_stakers_keeps_reserve() {
+			let mut sc = build_contract();
+			sc.max_stakers = 1;
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			assert!(sc.deposit(0).is_ok());
+			let reward_reserve = sc.get_metadata().reward_reserve;
+
+			set_caller(default_accounts().django);
+			assert_eq!(sc.deposit(0), Err(Error::TooManyStakers));
+			assert_eq!(sc.get_metadata().reward_reserve, reward_reserve);
+
+			set_caller(bob_id());
+			assert!(sc.deposit(0).is_ok());
+		}
+
+		#[test]
+		fn test_reward_reserve_backs_interest() {
+			let mut sc = build_contract();
+
+			set_caller(owner_id());
+			set_callee(contract_id());
+			set_account_balance(contract_id(), INITIAL_RESERVE);
+			assert!(sc.refund(INITIAL_RESERVE - 4).is_ok());
+			assert_eq!(sc.get_metadata().reward_reserve, 4);
+
+			set_caller(bob_id());
+			set_value_transferred(100);
+			assert_eq!(sc.deposit(0), Err(Error::InsufficientRewardReserve));
+
+			set_caller(owner_id());
+			set_value_transferred(6);
+			assert!(sc.fund_rewards().is_ok());
+			assert_eq!(sc.get_metadata().reward_reserve, 10);
+
+			set_caller(bob_id());
+			set_value_transferred(100);
+			assert!(sc.deposit(0).is_ok());
+			assert_eq!(sc.get_metadata().reward_reserve, 5);
+			set_account_balance(contract_id(), 110);
+
+			set_caller(owner_id());
+			assert_eq!(sc.refund(6), Err(Error::InsufficientContractBalance));
+			assert!(sc.refund(5).is_ok());
+			assert_eq!(sc.get_metadata().reward_reserve, 0);
+		}
+
+		#[test]
+		fn test_reward_token_pays_interest() {
+			let mut sc = build_token_contract(2 * MIL);
+			psp22::set_balance(token_id(), contract_id(), 10);
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(0).unwrap();
+			assert_eq!(lock_box.interest, 5);
+			assert_eq!(sc.get_metadata().reserved_reward_tokens, 10);
+
+			// Tokens are reserved for the first box
+			assert_eq!(sc.deposit(0), Err(Error::InsufficientRewardReserve));
+			assert_eq!(sc.get_metadata().reserved_reward_tokens, 10);
+
+			advance_block();
+			advance_block();
+			set_account_balance(contract_id(), 1000);
+			let balance_before = get_account_balance(bob_id()).unwrap();
+			// Only the native principal is returned, reward tokens are in the event
+			assert_eq!(sc.redeem(lock_box.id), Ok(100));
+
+			let event = test::recorded_events()
+				.last()
+				.expect("Missing RedeemSuccessful");
+			match <Event as scale::Decode>::decode(&mut &event.data[..]) {
+				Ok(Event::RedeemSuccessful(redeemed)) => {
+					assert_eq!(redeemed.staker, bob_id());
+					assert_eq!(redeemed.lock_box, lock_box);
+					assert_eq!(redeemed.reward, 10);
+				}
+				_ => panic!("Expected RedeemSuccessful"),
+			}
+
+			assert_eq!(get_account_balance(bob_id()).unwrap(), balance_before + 100);
+			assert_eq!(psp22::balance_of(token_id(), bob_id()), 10);
+			assert_eq!(psp22::balance_of(token_id(), contract_id()), 0);
+			assert_eq!(sc.get_metadata().reserved_reward_tokens, 0);
+		}
+
+		#[test]
+		fn test_reward_token_forfeited_interest_is_released() {
+			let mut sc = build_token_contract(MIL);
+			psp22::set_balance(token_id(), contract_id(), 5);
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(0).unwrap();
+			assert_eq!(sc.deposit(0), Err(Error::InsufficientRewardReserve));
+
+			// Whole interest is forfeited by the default penalty
+			set_account_balance(contract_id(), 1000);
+			assert_eq!(sc.early_withdraw(lock_box.id), Ok(100));
+			assert_eq!(sc.get_metadata().reserved_reward_tokens, 0);
+			assert_eq!(psp22::balance_of(token_id(), contract_id()), 5);
+
+			assert!(sc.deposit(0).is_ok());
+		}
+
+		#[test]
+		fn test_refund_with_reward_token() {
+			let mut sc = build_token_contract(MIL);
+			psp22::set_balance(token_id(), contract_id(), 5);
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			assert!(sc.deposit(0).is_ok());
+			set_account_balance(contract_id(), 150);
+
+			// Only the principal is locked, interest is owed in the reward token
+			set_caller(owner_id());
+			assert_eq!(sc.refund(51), Err(Error::InsufficientContractBalance));
+			assert!(sc.refund(50).is_ok());
+			assert_eq!(get_account_balance(contract_id()).unwrap(), 100);
+			assert_eq!(sc.get_metadata().reserved_reward_tokens, 5);
+		}
+
+		#[test]
+		#[should_panic(expected = "seal_return_value")]
+		fn test_failed_reward_transfer_reverts() {
+			let mut sc = build_token_contract(MIL);
+			psp22::set_balance(token_id(), contract_id(), 5);
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(0).unwrap();
+
+			psp22::set_balance(token_id(), contract_id(), 0);
+			advance_block();
+			advance_block();
+			set_account_balance(contract_id(), 1000);
+			assert_eq!(sc.get_box(lock_box.id), Some(lock_box));
+			assert_eq!(sc.get_metadata().reserved_reward_tokens, 5);
+
+			// Native principal can be paid but the reward token can't,
+			// so the message reverts as a whole
+			let _ = sc.redeem(lock_box.id);
+		}
+
+		#[test]
+		fn test_log2_permil_matches_known_values() {
+			assert_eq!(log2_permil(0), 0);
+			assert_eq!(log2_permil(1), 0);
+			assert_eq!(log2_permil(3), 1_584_962);
+			assert_eq!(log2_permil(10), 3_321_928);
+			assert_eq!(log2_permil(63), 5_977_279);
+			assert_eq!(log2_permil(1_000_000), 19_931_568);
+			assert_eq!(log2_permil(u128::MAX), 127_999_999);
+		}
+
+		#[test]
+		fn test_log2_permil_over_full_domain() {
+			for k in 0..128u32 {
+				let power = 1u128 << k;
+				assert_eq!(log2_permil(power), k as u128 * MIL);
+				assert!(log2_permil(power - 1) <= log2_permil(power));
+				if k > 0 {
+					assert!(log2_permil(power - 1) >= (k as u128 - 1) * MIL);
+					assert!(log2_permil(power + (power >> 1)) > log2_permil(power));
+				}
+				if k < 127 {
+					assert!(log2_permil(power + 1) >= log2_permil(power));
+				}
+			}
+
+			let mut previous = 0;
+			let mut num: u128 = 1;
+			while let Some(next) = num.checked_mul(3) {
+				let log = log2_permil(next);
+				assert!(log > previous);
+				previous = log;
+				num = next;
+			}
+		}
+
+		#[test]
+		fn test_interest_curves() {
+			assert_eq!(InterestCurve::Logarithmic.apply_permil(2), 1_584_962);
+			assert_eq!(InterestCurve::Linear.apply_permil(2), 2 * MIL);
+			assert_eq!(InterestCurve::Step.apply_permil(2), MIL);
+			assert_eq!(InterestCurve::Step.apply_permil(3), 2 * MIL);
+			assert_eq!(InterestCurve::Linear.apply_permil(u128::MAX), u128::MAX);
+		}
+
+		#[test]
+		fn test_transfer_lock_box() {
+			let mut sc = build_contract();
+			let django = default_accounts().django;
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let first = sc.deposit(0).unwrap();
+			let second = sc.deposit(0).unwrap();
+			assert_eq!(sc.total_supply(), 2);
+			assert_eq!(sc.get_metadata().stakers_count, 1);
+
+			set_caller(default_accounts().eve);
+			assert_eq!(
+				sc.transfer(django, Id::U128(first.id), vec![]),
+				Err(PSP34Error::NotApproved)
+			);
+
+			// Boxes owned by the contract itself could never be redeemed
+			set_caller(bob_id());
+			assert_eq!(
+				sc.transfer(contract_id(), Id::U128(first.id), vec![]),
+				Err(PSP34Error::Custom("SelfCall".into()))
+			);
+
+			assert!(sc.transfer(django, Id::U128(first.id), vec![]).is_ok());
+			assert_eq!(sc.owner_of(Id::U128(first.id)), Some(django));
+			assert_eq!(sc.balance_of(bob_id()), 1);
+			assert_eq!(sc.get_metadata().stakers_count, 2);
+
+			assert!(sc
+				.approve(alice_id(), Some(Id::U128(second.id)), true)
+				.is_ok());
+			set_caller(alice_id());
+			assert!(sc.transfer(django, Id::U128(second.id), vec![]).is_ok());
+			assert_eq!(sc.balance_of(django), 2);
+			assert_eq!(sc.get_metadata().stakers_count, 1);
+
+			advance_block();
+			advance_block();
+			set_account_balance(contract_id(), 1000u128);
+
+			set_caller(bob_id());
+			assert_eq!(sc.redeem(first.id), Err(Error::LockBoxNotFound));
+
+			set_caller(django);
+			assert_eq!(sc.redeem(first.id), Ok(105));
+			assert_eq!(sc.owner_of(Id::U128(first.id)), None);
+			assert_eq!(sc.total_supply(), 1);
+		}
+
+		#[test]
+		fn test_transfer_lock_box_to_new_staker_at_max_stakers() {
+			let mut sc = build_contract();
+			sc.max_stakers = 2;
+			let accounts = default_accounts();
+
+			set_callee(contract_id());
+			set_value_transferred(100);
+			set_caller(bob_id());
+			let first = sc.deposit(0).unwrap();
+			sc.deposit(0).unwrap();
+			set_caller(accounts.django);
+			let only = sc.deposit(0).unwrap();
+			assert_eq!(sc.get_metadata().stakers_count, 2);
+
+			set_caller(bob_id());
+			assert_eq!(
+				sc.transfer(accounts.eve, Id::U128(first.id), vec![]),
+				Err(Error::TooManyStakers.into())
+			);
+			assert_eq!(sc.owner_of(Id::U128(first.id)), Some(bob_id()));
+			assert_eq!(sc.balance_of(bob_id()), 2);
+			assert_eq!(sc.get_metadata().stakers_count, 2);
+
+			// Staker giving away its only box makes room for the new one
+			set_caller(accounts.django);
+			assert!(sc.transfer(accounts.eve, Id::U128(only.id), vec![]).is_ok());
+			assert_eq!(sc.get_metadata().stakers_count, 2);
+
+			set_caller(bob_id());
+			assert!(sc
+				.transfer(accounts.eve, Id::U128(first.id), vec![])
+				.is_ok());
+			assert_eq!(sc.balance_of(accounts.eve), 2);
+			assert_eq!(sc.get_metadata().stakers_count, 2);
+		}
+
+		#[test]
+		fn test_two_step_ownership_transfer() {
+			let mut sc = build_contract();
+			let eve = default_accounts().eve;
+
+			set_caller(bob_id());
+			assert_eq!(sc.transfer_ownership(bob_id()), Err(Error::NotOwner));
+
+			set_caller(owner_id());
+			assert!(sc.revoke_role(Role::Upgrader, owner_id()).is_ok());
+			assert!(sc.grant_role(Role::Upgrader, eve).is_ok());
+			assert!(sc.transfer_ownership(bob_id()).is_ok());
+			assert_eq!(sc.get_metadata().pending_owner, Some(bob_id()));
+
+			set_caller(default_accounts().django);
+			assert_eq!(sc.accept_ownership(), Err(Error::NotPendingOwner));
+
+			set_caller(bob_id());
+			let events_before = test::recorded_events().count();
+			assert!(sc.accept_ownership().is_ok());
+			assert_eq!(sc.get_metadata().owner, bob_id());
+			assert_eq!(sc.get_metadata().pending_owner, None);
+
+			// Roles of the previous owner moved to the new one, other holders keep theirs
+			for role in [Role::ConfigAdmin, Role::Pauser, Role::Treasurer] {
+				assert!(!sc.has_role(role, owner_id()));
+				assert!(sc.has_role(role, bob_id()));
+			}
+			assert!(!sc.has_role(Role::Upgrader, owner_id()));
+			assert!(!sc.has_role(Role::Upgrader, bob_id()));
+			assert!(sc.has_role(Role::Upgrader, eve));
+
+			let events = test::recorded_events()
+				.skip(events_before)
+				.map(|event| {
+					<Event as scale::Decode>::decode(&mut &event.data[..])
+						.expect("Invalid event data")
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(events.len(), 7);
+			match &events[0] {
+				Event::OwnershipTransferred(transferred) => {
+					assert_eq!(transferred.previous_owner, owner_id());
+					assert_eq!(transferred.new_owner, bob_id());
+				}
+				_ => panic!("Expected OwnershipTransferred"),
+			}
+			for (moved, role) in
+				events[1..]
+					.chunks(2)
+					.zip([Role::ConfigAdmin, Role::Pauser, Role::Treasurer])
+			{
+				match moved {
+					[Event::RoleRevoked(revoked), Event::RoleGranted(granted)] => {
+						assert_eq!((revoked.role, revoked.account), (role, owner_id()));
+						assert_eq!((granted.role, granted.account), (role, bob_id()));
+					}
+					_ => panic!("Expected RoleRevoked and RoleGranted"),
+				}
+			}
+
+			set_caller(owner_id());
+			assert_eq!(
+				sc.grant_role(Role::Pauser, owner_id()),
+				Err(Error::NotOwner)
+			);
+		}
+
+		#[test]
+		fn test_roles_are_separated() {
+			let mut sc = build_contract();
+			let django = default_accounts().django;
+
+			set_caller(owner_id());
+			assert!(sc.grant_role(Role::Pauser, django).is_ok());
+			assert!(sc.revoke_role(Role::Pauser, owner_id()).is_ok());
+			assert!(sc.has_role(Role::Pauser, django));
+			assert!(!sc.has_role(Role::Pauser, owner_id()));
+			assert_eq!(sc.pause_contract(), Err(Error::MissingRole));
+			assert!(sc.add_tier(18, 10_000_000, 150).is_ok());
+
+			set_caller(django);
+			assert!(sc.pause_contract().is_ok());
+			assert_eq!(sc.add_tier(18, 10_000_000, 150), Err(Error::MissingRole));
+			assert_eq!(sc.set_code([0; 32]), Err(Error::MissingRole));
+			assert_eq!(sc.refund(1), Err(Error::MissingRole));
+		}
+
+		#[test]
+		fn test_admin_changes_emit_events() {
+			let mut sc = build_contract();
+			let events_before = test::recorded_events().count();
+
+			set_caller(owner_id());
+			set_callee(contract_id());
+			let config = sc.config();
+			assert!(sc
+				.configure_contract(None, None, None, Some(2), None, None, None, None)
+				.is_ok());
+			let tier_id = sc.add_tier(18, 10_000_000, 150).unwrap();
+			let tier = sc.get_tier(tier_id).unwrap();
+			assert!(sc.configure_tier(tier_id, None, None, Some(300)).is_ok());
+			assert!(sc.pause_contract().is_ok());
+			assert!(sc.resume_contract().is_ok());
+			set_value_transferred(6);
+			assert!(sc.fund_rewards().is_ok());
+			assert!(sc.refund(INITIAL_BALANCE).is_ok());
+
+			let old_box = LockBox {
+				id: 0,
+				tier: 0,
+				created_at: 0,
+				deposit: 0,
+				interest: 0,
+				release: 0,
+			};
+			sc.lock_boxes.insert(old_box.id, &old_box);
+			let lock_box = LockBox {
+				release: 10,
+				..old_box
+			};
+			assert!(sc.set_box(lock_box).is_ok());
+
+			let events = test::recorded_events()
+				.skip(events_before)
+				.map(|event| {
+					<Event as scale::Decode>::decode(&mut &event.data[..])
+						.expect("Invalid event data")
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(events.len(), 8);
+
+			match &events[0] {
+				Event::ContractConfigured(ContractConfigured { old, new }) => {
+					assert_eq!(old, &config);
+					assert_eq!(
+						new,
+						&ContractConfig {
+							stakers_sample: 2,
+							..config
+						}
+					);
+				}
+				_ => panic!("Expected ContractConfigured"),
+			}
+			match &events[1] {
+				Event::TierSet(TierSet { id, old, new }) => {
+					assert_eq!(*id, tier_id);
+					assert_eq!(old, &None);
+					assert_eq!(new, &tier);
+				}
+				_ => panic!("Expected TierSet"),
+			}
+			match &events[2] {
+				Event::TierSet(TierSet { id, old, new }) => {
+					assert_eq!(*id, tier_id);
+					assert_eq!(old, &Some(tier));
+					assert_eq!(
+						new,
+						&Tier {
+							capacity: 300,
+							..tier
+						}
+					);
+				}
+				_ => panic!("Expected TierSet"),
+			}
+			match (&events[3], &events[4]) {
+				(Event::PauseChanged(paused), Event::PauseChanged(resumed)) => {
+					assert_eq!(paused.account, owner_id());
+					assert!(!paused.old && paused.new);
+					assert!(resumed.old && !resumed.new);
+				}
+				_ => panic!("Expected PauseChanged"),
+			}
+			match &events[5] {
+				Event::RewardsFunded(funded) => {
+					assert_eq!(funded.account, owner_id());
+					assert_eq!(funded.amount, 6);
+					assert_eq!(funded.old_reward_reserve, INITIAL_RESERVE);
+					assert_eq!(funded.new_reward_reserve, INITIAL_RESERVE + 6);
+				}
+				_ => panic!("Expected RewardsFunded"),
+			}
+			match &events[6] {
+				Event::Refunded(refunded) => {
+					assert_eq!(refunded.account, owner_id());
+					assert_eq!(refunded.amount, INITIAL_BALANCE);
+					assert_eq!(refunded.old_reward_reserve, INITIAL_RESERVE + 6);
+					assert_eq!(
+						refunded.new_reward_reserve,
+						INITIAL_RESERVE + 6 - INITIAL_BALANCE
+					);
+				}
+				_ => panic!("Expected Refunded"),
+			}
+			match &events[7] {
+				Event::LockBoxSet(LockBoxSet { old, new }) => {
+					assert_eq!(old, &old_box);
+					assert_eq!(new, &lock_box);
+				}
+				_ => panic!("Expected LockBoxSet"),
+			}
+		}
+
+		#[test]
+		fn test_set_box_only_moves_release() {
+			let mut sc = build_contract();
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(0).unwrap();
+
+			set_caller(owner_id());
+			let missing_box = LockBox {
+				id: lock_box.id + 1,
+				..lock_box
+			};
+			assert_eq!(sc.set_box(missing_box), Err(Error::LockBoxNotFound));
+			let more_interest = LockBox {
+				interest: lock_box.interest + 1,
+				..lock_box
+			};
+			assert_eq!(sc.set_box(more_interest), Err(Error::InvalidLockBoxChange));
+			let smaller_deposit = LockBox {
+				deposit: 1,
+				..lock_box
+			};
+			assert_eq!(
+				sc.set_box(smaller_deposit),
+				Err(Error::InvalidLockBoxChange)
+			);
+			assert_eq!(sc.get_box(lock_box.id), Some(lock_box));
+
+			// Released box can be redeemed with its interest paid from the reserve
+			let released = LockBox {
+				release: 0,
+				..lock_box
+			};
+			assert!(sc.set_box(released).is_ok());
+			assert_eq!(sc.get_box(lock_box.id), Some(released));
+
+			set_caller(bob_id());
+			set_account_balance(contract_id(), 1000);
+			assert_eq!(sc.redeem(lock_box.id), Ok(100 + lock_box.interest));
+			assert_eq!(sc.get_metadata().unclaimed_rewards, 0);
+		}
+
+		#[test]
+		fn test_deposit_in_tiers() {
+			let mut sc = build_contract();
+
+			set_caller(owner_id());
+			let tier_id = sc.add_tier(18, 10_000_000, 150).unwrap();
+			assert_eq!(tier_id, 1);
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(tier_id).unwrap();
+			assert_eq!(lock_box.tier, tier_id);
+			assert_eq!(lock_box.interest, 10);
+			assert_eq!(lock_box.release, lock_box.created_at + 18);
+
+			assert_eq!(sc.deposit(tier_id), Err(Error::TierCapacityReached));
+			assert_eq!(sc.deposit(2), Err(Error::TierNotFound));
+
+			let tiers = sc.get_metadata().tiers;
+			assert_eq!(tiers.len(), 2);
+			assert_eq!(tiers[0].tier.total_liquidity, 0);
+			assert_eq!(tiers[1].tier.total_liquidity, 100);
+			assert_eq!(tiers[1].tier.lock_boxes_count, 1);
+		}
+
+		#[test]
+		fn test_interest_of_large_deposit_does_not_overflow() {
+			let mut sc = build_contract();
+			let value = u128::MAX / 8;
+
+			set_caller(owner_id());
+			set_callee(contract_id());
+			sc.configure_contract(
+				Some(u128::MAX),
+				Some(u128::MAX),
+				None,
+				None,
+				None,
+				None,
+				None,
+				None,
+			)
+			.unwrap();
+			let tier_id = sc.add_tier(6, 5_000_000, u128::MAX).unwrap();
+			set_value_transferred(value);
+			sc.fund_rewards().unwrap();
+
+			set_caller(bob_id());
+			set_value_transferred(value);
+			let lock_box = sc.deposit(tier_id).unwrap();
+			assert_eq!(lock_box.interest, value / (100 * MIL) * 5_000_000);
+		}
+
+		#[test]
+		fn test_early_withdraw_pays_accrued_interest_minus_penalty() {
+			let mut sc = build_contract();
+
+			set_caller(owner_id());
+			let tier_id = sc.add_tier(12, 10_000_000, MAX_DEPOSIT_VALUE).unwrap();
+			sc.configure_contract(None, None, None, None, None, None, None, Some(20_000_000))
+				.unwrap();
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(tier_id).unwrap();
+
+			advance_block();
+			let elapsed = test::get_block_timestamp::<DefaultEnvironment>() - lock_box.created_at;
+			let accrued = 10 * elapsed as u128 / 12;
+			assert_eq!(sc.get_accrued_interest(lock_box.id), Some(accrued));
+
+			set_account_balance(contract_id(), 1000u128);
+			let balance_before = get_account_balance(bob_id()).unwrap();
+			let paid = sc.early_withdraw(lock_box.id).unwrap();
+
+			assert_eq!(paid, 100 + accrued - accrued * 20 / 100);
+			assert_eq!(
+				get_account_balance(bob_id()).unwrap(),
+				balance_before + paid
+			);
+			assert_eq!(sc.get_metadata().unclaimed_rewards, 0);
+		}
+
+		#[test]
+		fn test_partial_withdraw_after_release() {
+			let mut sc = build_contract();
+
+			set_caller(bob_id());
+			set_callee(contract_id());
+			set_value_transferred(100);
+			let lock_box = sc.deposit(0).unwrap();
+
+			advance_block();
+			advance_block();
+
+			set_account_balance(contract_id(), 1000u128);
+			assert_eq!(
+				sc.partial_withdraw(lock_box.id, 0),
+				Err(Error::InvalidAmount)
+			);
+			assert_eq!(
+				sc.partial_withdraw(lock_box.id, 101),
+				Err(Error::InvalidAmount)
+			);
+			assert_eq!(sc.partial_withdraw(lock_box.id, 40), Ok(42));
+
+			let lock_box = sc.get_box(lock_box.id).unwrap();
+			assert_eq!(lock_box.deposit, 60);
+			assert_eq!(lock_box.interest, 3);
+			assert_eq!(sc.get_metadata().total_liquidity, 60);
+
+			assert_eq!(sc.partial_withdraw(lock_box.id, 60), Ok(63));
+			assert_eq!(sc.get_box(lock_box.id), None);
 		}
 	}
 }
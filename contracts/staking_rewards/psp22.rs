@@ -0,0 +1,93 @@
+use ink_env::AccountId;
+#[cfg(not(test))]
+use ink_env::{
+	call::{build_call, Call, ExecutionInput, Selector},
+	DefaultEnvironment,
+};
+use ink_prelude::string::String;
+#[cfg(not(test))]
+use ink_prelude::vec::Vec;
+
+use crate::psp34::Balance;
+
+#[cfg(test)]
+pub use mock::{balance_of, set_balance, transfer};
+
+/// Selector of `PSP22::balance_of`
+#[cfg(not(test))]
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x65, 0x68, 0x38, 0x2f];
+/// Selector of `PSP22::transfer`
+#[cfg(not(test))]
+const TRANSFER_SELECTOR: [u8; 4] = [0xdb, 0x20, 0xf9, 0xf5];
+
+#[derive(Clone, Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+	Custom(String),
+	InsufficientBalance,
+	InsufficientAllowance,
+	ZeroRecipientAddress,
+	ZeroSenderAddress,
+	SafeTransferCheckFailed(String),
+}
+
+/// Balance of `owner` in the PSP22 `token`, `0` when the call fails
+#[cfg(not(test))]
+pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+	build_call::<DefaultEnvironment>()
+		.call_type(Call::new().callee(token).gas_limit(0))
+		.exec_input(ExecutionInput::new(Selector::new(BALANCE_OF_SELECTOR)).push_arg(owner))
+		.returns::<Balance>()
+		.fire()
+		.unwrap_or(0)
+}
+
+/// Transfers `value` of the PSP22 `token` from the calling contract to `to`
+#[cfg(not(test))]
+pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> Result<(), PSP22Error> {
+	build_call::<DefaultEnvironment>()
+		.call_type(Call::new().callee(token).gas_limit(0))
+		.exec_input(
+			ExecutionInput::new(Selector::new(TRANSFER_SELECTOR))
+				.push_arg(to)
+				.push_arg(value)
+				.push_arg(Vec::<u8>::new()),
+		)
+		.returns::<Result<(), PSP22Error>>()
+		.fire()
+		.map_err(|_| PSP22Error::Custom(String::from("CallFailed")))?
+}
+
+/// The off-chain environment cannot call other contracts,
+/// so tests use an in-memory ledger of PSP22 balances instead
+#[cfg(test)]
+mod mock {
+	use super::{AccountId, Balance, PSP22Error};
+	use ink_env::DefaultEnvironment;
+	use std::{cell::RefCell, collections::BTreeMap};
+
+	thread_local! {
+		static BALANCES: RefCell<BTreeMap<(AccountId, AccountId), Balance>> =
+			RefCell::new(BTreeMap::new());
+	}
+
+	pub fn set_balance(token: AccountId, owner: AccountId, value: Balance) {
+		BALANCES.with(|balances| balances.borrow_mut().insert((token, owner), value));
+	}
+
+	pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+		BALANCES.with(|balances| balances.borrow().get(&(token, owner)).copied().unwrap_or(0))
+	}
+
+	pub fn transfer(token: AccountId, to: AccountId, value: Balance) -> Result<(), PSP22Error> {
+		let from = ink_env::account_id::<DefaultEnvironment>();
+		let from_balance = balance_of(token, from);
+		if from_balance < value {
+			return Err(PSP22Error::InsufficientBalance);
+		}
+
+		set_balance(token, from, from_balance - value);
+		set_balance(token, to, balance_of(token, to) + value);
+		Ok(())
+	}
+}
@@ -0,0 +1,57 @@
+use ink_env::{AccountId, DefaultEnvironment, Environment};
+use ink_lang as ink;
+use ink_prelude::{string::String, vec::Vec};
+
+pub type Balance = <DefaultEnvironment as Environment>::Balance;
+
+#[derive(Clone, Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Id {
+	U8(u8),
+	U16(u16),
+	U32(u32),
+	U64(u64),
+	U128(u128),
+	Bytes(Vec<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+	Custom(String),
+	SelfApprove,
+	NotApproved,
+	TokenExists,
+	TokenNotExists,
+	SafeTransferCheckFailed(String),
+}
+
+/// PSP34 non-fungible token standard, selectors match the `PSP34::<message>` ones
+#[ink::trait_definition]
+pub trait PSP34 {
+	#[ink(message)]
+	fn collection_id(&self) -> Id;
+
+	#[ink(message)]
+	fn balance_of(&self, owner: AccountId) -> u32;
+
+	#[ink(message)]
+	fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+	#[ink(message)]
+	fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+	#[ink(message)]
+	fn approve(
+		&mut self,
+		operator: AccountId,
+		id: Option<Id>,
+		approved: bool,
+	) -> Result<(), PSP34Error>;
+
+	#[ink(message)]
+	fn transfer(&mut self, to: AccountId, id: Id, data: Vec<u8>) -> Result<(), PSP34Error>;
+
+	#[ink(message)]
+	fn total_supply(&self) -> Balance;
+}
@@ -184,23 +184,37 @@ pub const BENCHMARK_SAMPLES: [BenchmarkSample; 4] = [
 
 const CREDITOR_KEY: sr25519::Public = sr25519::Public([1; 32]);
 
-fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
 benchmarks! {
 	set_airdrop_server_account {
-				let old_account: types::AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+				let account: types::AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+				let quota = types::ServerQuota {
+					claims: Some((100_u32, types::QuotaPeriod::PerDay)),
+					total_amount: Some(10_000_u32.into()),
+				};
 
-				let new_account: types::AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+			}: set_airdrop_server_account(RawOrigin::Root, account.clone(), quota.clone())
+	verify {
+				assert_last_event::<T>(Event::ServerAccountSet{
+					account: account.clone(),
+					quota,
+				}.into());
+	}
 
-				<ServerAccount<T>>::set(Some(old_account.clone()));
+	remove_airdrop_server_account {
+				let account: types::AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
+				<ServerAccounts<T>>::insert(
+					&account,
+					types::ServerAccountInfo::new(Default::default(), Default::default()),
+				);
 
-			}: set_airdrop_server_account(RawOrigin::Root,new_account.clone())
+			}: remove_airdrop_server_account(RawOrigin::Root, account.clone())
 	verify {
-				assert_last_event::<T>(Event::ServerAccountChanged{
-					old_account:Some(old_account.clone()),
-					new_account:new_account.clone()
+				assert_last_event::<T>(Event::ServerAccountRemoved{
+					account: account.clone(),
 				}.into());
 	}
 
@@ -216,24 +230,56 @@ benchmarks! {
 		}.into());
 	}
 
+	set_claim_rate_limit {
+		let old_limit = Pallet::<T>::get_claim_rate_limit();
+		let new_limit = Some(types::ClaimRateLimit {
+			max_amount: 10_000_u32.into(),
+			period: 100_u32.into(),
+		});
+
+	}: set_claim_rate_limit(RawOrigin::Root, new_limit.clone())
+	verify {
+		 assert_last_event::<T>(Event::ClaimRateLimitChanged {
+			old_limit,
+			new_limit,
+		}.into());
+	}
+
+
 
 	dispatch_user_claim {
 		let x in 0 .. 3;
 		let caller: types::AccountIdOf<T> = frame_benchmarking::whitelisted_caller();
-		// let ofw_account = sr25519::Public([1; 32]).into_account();
-		Pallet::<T>::set_creditor_account(creditor_key);
+		Pallet::<T>::set_creditor_account(CREDITOR_KEY);
 		let system_account_id = Pallet::<T>::get_creditor_account().unwrap();
 		Pallet::<T>::init_balance(&system_account_id,10_000_000_000_000_000_000_000_000);
-		let case= UserClaimTestCase::<<T as pallet::Config>::MaxProofSize>::try_from(benchmark_samples[x as usize].clone()).unwrap();
+		let case= UserClaimTestCase::<<T as pallet::Config>::MaxProofSize>::try_from(BENCHMARK_SAMPLES[x as usize].clone()).unwrap();
 		let amount = <T::BalanceTypeConversion as Convert<_, _>>::convert(case.amount);
+		MerkleRoot::<T>::put(case.merkle_root);
 		 let icon_address=case.icon_address.clone();
 		 let mut new_state = types::AirdropState::default();
 		 new_state.block_claim_request=false;
 		 new_state.block_exchange_request=false;
 		<AirdropChainState<T>>::set(new_state.clone());
+		// Server account with both quotas is the most expensive origin to check
+		<ServerAccounts<T>>::insert(
+			&caller,
+			types::ServerAccountInfo::new(
+				types::ServerQuota {
+					claims: Some((100_u32, types::QuotaPeriod::PerDay)),
+					total_amount: Some(amount),
+				},
+				Default::default(),
+			),
+		);
+		// Claim is counted against the rate limit but still fits in it
+		<ClaimRateLimit<T>>::put(types::ClaimRateLimit {
+			max_amount: amount,
+			period: 100_u32.into(),
+		});
 
 	}: dispatch_user_claim(
-		RawOrigin::Root,
+		RawOrigin::Signed(caller),
 		case.icon_address,
 		case.ice_address,
 		case.message,
@@ -244,16 +290,18 @@ benchmarks! {
 		case.merkle_proofs)
 	verify {
 		assert_last_event::<T>(Event::ClaimSuccess(icon_address.clone()).into());
+		assert_eq!(Pallet::<T>::get_claim_volume().amount, amount);
 	}
 
 	dispatch_exchange_claim {
 		let x in 0 .. 3;
 
-		Pallet::<T>::set_creditor_account(creditor_key);
+		Pallet::<T>::set_creditor_account(CREDITOR_KEY);
 		let system_account_id = Pallet::<T>::get_creditor_account().unwrap();
 		Pallet::<T>::init_balance(&system_account_id,10_000_000_000_000_000_000_000_000);
-		let case= UserClaimTestCase::<<T as pallet::Config>::MaxProofSize>::try_from(benchmark_samples[x as usize].clone()).unwrap();
+		let case= UserClaimTestCase::<<T as pallet::Config>::MaxProofSize>::try_from(BENCHMARK_SAMPLES[x as usize].clone()).unwrap();
 		let amount = <T::BalanceTypeConversion as Convert<_, _>>::convert(case.amount);
+		MerkleRoot::<T>::put(case.merkle_root);
 		let icon_address=case.icon_address.clone();
 		<ExchangeAccountsMap<T>>::insert(icon_address.clone(),amount);
 		let mut new_state = types::AirdropState::default();
@@ -288,5 +336,22 @@ benchmarks! {
 		}.into());
 	}
 
-	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+	change_merkle_root_with_scheme {
+		let p in 0..10;
+		let new_root = [p as u8;32];
+		let last_root = [0u8;32];
+		MerkleRoot::<T>::put(last_root.clone());
+		MerkleHashingScheme::<T>::put(types::MerkleHashScheme::Blake2);
+	}: change_merkle_root_with_scheme(
+		RawOrigin::Root,
+		new_root,
+		types::MerkleHashScheme::Keccak
+	) verify {
+		assert_last_event::<T>(Event::MerkleHashSchemeUpdated{
+			old_scheme: types::MerkleHashScheme::Blake2,
+			new_scheme: types::MerkleHashScheme::Keccak,
+		}.into());
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::tests::mock::new_test_ext(), crate::tests::mock::Test);
 }
@@ -21,8 +21,14 @@ pub mod merkle;
 
 mod exchange_accounts;
 
+/// Storage migrations of this pallet
+pub mod migrations;
+
 pub mod transfer;
 
+/// Evm precompile to expose airdrop storage and claim to evm side
+pub mod precompile;
+
 #[cfg(not(test))]
 pub(crate) use log::{error, info};
 #[cfg(test)]
@@ -32,7 +38,7 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
 	use super::{error, info};
-	use super::{exchange_accounts, transfer, types, utils, weights};
+	use super::{exchange_accounts, migrations, transfer, types, utils, weights};
 	use hex_literal::hex;
 	use sp_runtime::traits::Convert;
 
@@ -43,12 +49,12 @@ pub mod pallet {
 	use crate::merkle;
 	use crate::types::MerkelProofValidator;
 	use frame_support::storage::bounded_vec::BoundedVec;
-	use frame_support::traits::{Currency, LockableCurrency, ReservableCurrency};
-	use sp_runtime::traits::Verify;
+	use frame_support::traits::{Currency, LockableCurrency, ReservableCurrency, UnixTime};
+	use sp_runtime::traits::{CheckedAdd, Saturating, Verify};
 	use weights::WeightInfo;
 
 	// Re-exports
-	pub use types::VestingTerms;
+	pub use types::{VestingPeriod, VestingTerms};
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -76,6 +82,21 @@ pub mod pallet {
 
 		type MaxProofSize: Get<u32>;
 
+		/// Maximum number of server accounts that can be authorized at once
+		type MaxServerAccounts: Get<u32>;
+
+		/// Number of blocks in a day. Used to count per-day claim quota of server account
+		type BlocksPerDay: Get<types::BlockNumberOf<Self>>;
+
+		/// Source of current time. Used to record when vesting duration was converted to blocks
+		type UnixTime: UnixTime;
+
+		/// Expected block time in milliseconds.
+		/// Used to convert wall-clock vesting duration to number of blocks.
+		/// Vesting schedules are defined in blocks, so this is an estimate and
+		/// is not corrected by the actual time blocks are produced at
+		type ExpectedBlockTime: Get<u64>;
+
 		const VESTING_TERMS: VestingTerms;
 	}
 
@@ -92,13 +113,24 @@ pub mod pallet {
 		/// PartialClaimRequest have been ok for given icon address
 		ClaimPartialSuccess(types::IconAddress),
 
-		/// Value of ServerAccount storage have been changed
-		// Return old value and new one
-		ServerAccountChanged {
-			old_account: Option<types::AccountIdOf<T>>,
-			new_account: types::AccountIdOf<T>,
+		/// Server account have been authorized or it's quota have been updated
+		ServerAccountSet {
+			account: types::AccountIdOf<T>,
+			quota: types::ServerQuotaOf<T>,
 		},
 
+		/// Server account is no longer authorized
+		ServerAccountRemoved { account: types::AccountIdOf<T> },
+
+		/// Server account have dispatched all the claims allowed in current period
+		ServerClaimQuotaReached {
+			account: types::AccountIdOf<T>,
+			period: types::QuotaPeriod,
+		},
+
+		/// Server account have dispatched all the amount allowed to it
+		ServerAmountQuotaReached { account: types::AccountIdOf<T> },
+
 		/// AirdropState have been updated
 		AirdropStateUpdated {
 			old_state: types::AirdropState,
@@ -111,8 +143,28 @@ pub mod pallet {
 			new_root: [u8; 32],
 		},
 
+		/// Hashing scheme of merkle tree have been changed
+		MerkleHashSchemeUpdated {
+			old_scheme: types::MerkleHashScheme,
+			new_scheme: types::MerkleHashScheme,
+		},
+
 		/// Creditor balance is running low
 		CreditorBalanceLow,
+
+		/// Claim rate limit have been changed
+		ClaimRateLimitChanged {
+			old_limit: Option<types::ClaimRateLimitOf<T>>,
+			new_limit: Option<types::ClaimRateLimitOf<T>>,
+		},
+
+		/// Claimed amount in current window would exceed the rate limit
+		/// so new claim requests have been blocked
+		ClaimRateLimitExceeded {
+			window_start: types::BlockNumberOf<T>,
+			claimed_amount: types::BalanceOf<T>,
+			requested_amount: types::BalanceOf<T>,
+		},
 	}
 
 	#[pallet::storage]
@@ -130,8 +182,13 @@ pub mod pallet {
 		StorageMap<_, Twox64Concat, types::AccountIdOf<T>, types::IconAddress, OptionQuery>;
 
 	#[pallet::storage]
-	#[pallet::getter(fn get_airdrop_server_account)]
-	pub(super) type ServerAccount<T: Config> = StorageValue<_, types::AccountIdOf<T>, OptionQuery>;
+	pub(super) type ServerAccounts<T: Config> = CountedStorageMap<
+		_,
+		Twox64Concat,
+		types::AccountIdOf<T>,
+		types::ServerAccountInfoOf<T>,
+		OptionQuery,
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_exchange_account)]
@@ -142,11 +199,27 @@ pub mod pallet {
 	#[pallet::getter(fn try_get_merkle_root)]
 	pub type MerkleRoot<T: Config> = StorageValue<_, [u8; 32], OptionQuery>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn get_claim_rate_limit)]
+	pub type ClaimRateLimit<T: Config> = StorageValue<_, types::ClaimRateLimitOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_claim_volume)]
+	pub type ClaimVolume<T: Config> = StorageValue<_, types::ClaimVolumeOf<T>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn get_merkle_hash_scheme)]
+	pub type MerkleHashingScheme<T: Config> = StorageValue<_, types::MerkleHashScheme, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn try_get_creditor_account)]
 	pub(super) type CreditorAccount<T: Config> =
 		StorageValue<_, types::AccountIdOf<T>, OptionQuery>;
 
+	#[pallet::storage]
+	pub(super) type VestingMigrationCursor<T: Config> =
+		StorageValue<_, types::MigrationCursor, OptionQuery>;
+
 	#[pallet::type_value]
 	pub(super) fn DefaultStorageVersion<T: Config>() -> u32 {
 		1_u32.into()
@@ -228,6 +301,29 @@ pub mod pallet {
 
 		/// Claim amount was not expected in this exchanged airdrop
 		InvalidClaimAmount,
+
+		/// Maximum number of server accounts are already authorized
+		TooManyServerAccounts,
+
+		/// Given account is not an authorized server account
+		UnknownServerAccount,
+
+		/// Server account have exhausted it's claim or amount quota
+		ServerQuotaExceeded,
+
+		/// Snapshots are still being migrated to latest storage version
+		MigrationInProgress,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_server_accounts::<T>()
+		}
+
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			migrations::migrate_to_vesting_conversion::<T>()
+		}
 	}
 
 	#[pallet::call]
@@ -251,11 +347,23 @@ pub mod pallet {
 			proofs: types::MerkleProofs<T>,
 		) -> DispatchResultWithPostInfo {
 			// Make sure only root or server account call call this
-			Self::ensure_root_or_server(origin).map_err(|_| Error::<T>::DeniedOperation)?;
+			// and server account is still within it's quota
+			Self::ensure_root_or_server(origin, total_amount).map_err(|e| match e {
+				DispatchError::BadOrigin => Error::<T>::DeniedOperation.into(),
+				e => e,
+			})?;
 
 			// Make sure node is accepting new claim-request
 			Self::ensure_user_claim_switch()?;
 
+			// Stop accepting claims if this claim exceed the rate limit.
+			// We can't return an error here as that would revert blocking of claims.
+			// Snapshot stays unclaimed so user can claim once claims are re-opened
+			if !Self::register_claim_volume(total_amount) {
+				error!("claim request by: {icon_address:?}. Claim rate limit exceeded. Blocking new claims.");
+				return Ok(Pays::No.into());
+			}
+
 			// Verify the integrity of message
 			Self::validate_message_payload(&message, &ice_address).map_err(|e| {
 				info!(
@@ -375,42 +483,88 @@ pub mod pallet {
 			Ok(Pays::No.into())
 		}
 
+		/// Authorize new server account with given quota
+		/// or update the quota if this account is already authorized
 		#[pallet::weight(<T as Config>::AirdropWeightInfo::set_airdrop_server_account())]
 		pub fn set_airdrop_server_account(
 			origin: OriginFor<T>,
-			new_account: types::AccountIdOf<T>,
+			account: types::AccountIdOf<T>,
+			quota: types::ServerQuotaOf<T>,
 		) -> DispatchResultWithPostInfo {
 			ensure_root(origin).map_err(|_| Error::<T>::DeniedOperation)?;
 
-			let old_account = Self::get_airdrop_server_account();
-			<ServerAccount<T>>::set(Some(new_account.clone()));
+			let server_info = match Self::get_airdrop_server_account(&account) {
+				Some(mut server_info) => {
+					server_info.quota = quota.clone();
+					server_info
+				}
+				None => {
+					ensure!(
+						<ServerAccounts<T>>::count() < T::MaxServerAccounts::get(),
+						Error::<T>::TooManyServerAccounts
+					);
+					types::ServerAccountInfo::new(
+						quota.clone(),
+						utils::get_current_block_number::<T>(),
+					)
+				}
+			};
+			<ServerAccounts<T>>::insert(&account, server_info);
 
 			info!(
-				"Server account changed from {old_account:?} to {new_account:?} at height: {bl_num:?}",
+				"Server account {account:?} set with quota {quota:?} at height: {bl_num:?}",
 				bl_num = utils::get_current_block_number::<T>(),
 			);
 
-			Self::deposit_event(Event::ServerAccountChanged {
-				old_account,
-				new_account,
-			});
+			Self::deposit_event(Event::ServerAccountSet { account, quota });
 
 			Ok(Pays::No.into())
 		}
 
-		#[pallet::weight(<T as Config>::AirdropWeightInfo::change_merkle_root())]
-		pub fn change_merkle_root(origin: OriginFor<T>, new_root: [u8; 32]) -> DispatchResult {
+		#[pallet::weight(<T as Config>::AirdropWeightInfo::remove_airdrop_server_account())]
+		pub fn remove_airdrop_server_account(
+			origin: OriginFor<T>,
+			account: types::AccountIdOf<T>,
+		) -> DispatchResultWithPostInfo {
 			ensure_root(origin).map_err(|_| Error::<T>::DeniedOperation)?;
-			let old_root = Self::try_get_merkle_root();
 
-			MerkleRoot::<T>::put(&new_root);
+			ensure!(
+				<ServerAccounts<T>>::contains_key(&account),
+				Error::<T>::UnknownServerAccount
+			);
+			<ServerAccounts<T>>::remove(&account);
 
 			info!(
-				"Merkle root changed from {old_root:?} to {new_root:?} at height {bl_num:?}",
-				bl_num = utils::get_current_block_number::<T>()
+				"Server account {account:?} removed at height: {bl_num:?}",
+				bl_num = utils::get_current_block_number::<T>(),
 			);
 
-			Self::deposit_event(Event::<T>::MerkleRootUpdated { old_root, new_root });
+			Self::deposit_event(Event::ServerAccountRemoved { account });
+
+			Ok(Pays::No.into())
+		}
+
+		/// Change the merkle root. Tree of this root is expected to be
+		/// constructed with default blake2 scheme
+		#[pallet::weight(<T as Config>::AirdropWeightInfo::change_merkle_root())]
+		pub fn change_merkle_root(origin: OriginFor<T>, new_root: [u8; 32]) -> DispatchResult {
+			ensure_root(origin).map_err(|_| Error::<T>::DeniedOperation)?;
+
+			Self::do_change_merkle_root(new_root, types::MerkleHashScheme::default());
+			Ok(())
+		}
+
+		/// Change the merkle root along with the hashing scheme
+		/// with which the tree of this root was constructed
+		#[pallet::weight(<T as Config>::AirdropWeightInfo::change_merkle_root_with_scheme())]
+		pub fn change_merkle_root_with_scheme(
+			origin: OriginFor<T>,
+			new_root: [u8; 32],
+			scheme: types::MerkleHashScheme,
+		) -> DispatchResult {
+			ensure_root(origin).map_err(|_| Error::<T>::DeniedOperation)?;
+
+			Self::do_change_merkle_root(new_root, scheme);
 			Ok(())
 		}
 
@@ -437,6 +591,31 @@ pub mod pallet {
 
 			Ok(Pays::No.into())
 		}
+
+		/// Set maximum amount that can be paid by user claims within given number of blocks.
+		/// Passing `None` removes the limit
+		#[pallet::weight(<T as Config>::AirdropWeightInfo::set_claim_rate_limit())]
+		pub fn set_claim_rate_limit(
+			origin: OriginFor<T>,
+			new_limit: Option<types::ClaimRateLimitOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin).map_err(|_| Error::<T>::DeniedOperation)?;
+
+			let old_limit = Self::get_claim_rate_limit();
+			<ClaimRateLimit<T>>::set(new_limit.clone());
+
+			info!(
+				"Claim rate limit changed from {old_limit:?} to {new_limit:?} at height: {bl_num:?}",
+				bl_num = utils::get_current_block_number::<T>(),
+			);
+
+			Self::deposit_event(Event::ClaimRateLimitChanged {
+				old_limit,
+				new_limit,
+			});
+
+			Ok(Pays::No.into())
+		}
 	}
 
 	// implement all the helper function that are called from pallet dispatchable
@@ -453,6 +632,51 @@ pub mod pallet {
 			}
 		}
 
+		/// Count given amount against the claim rate limit.
+		/// If the limit would be exceeded, new claim requests are blocked
+		/// and false is returned. Only root can re-open claims after that
+		pub fn register_claim_volume(amount: types::BalanceOf<T>) -> bool {
+			let limit = match Self::get_claim_rate_limit() {
+				Some(limit) => limit,
+				None => return true,
+			};
+
+			let now = utils::get_current_block_number::<T>();
+			let mut volume = Self::get_claim_volume();
+			if now >= volume.window_start.saturating_add(limit.period) {
+				volume = types::ClaimVolume {
+					window_start: now,
+					amount: Default::default(),
+				};
+			}
+
+			let claimed_amount = volume.amount.saturating_add(amount);
+			if claimed_amount > limit.max_amount {
+				let old_state = Self::get_airdrop_state();
+				let new_state = types::AirdropState {
+					block_claim_request: true,
+					..old_state.clone()
+				};
+				<AirdropChainState<T>>::set(new_state.clone());
+
+				Self::deposit_event(Event::<T>::ClaimRateLimitExceeded {
+					window_start: volume.window_start,
+					claimed_amount: volume.amount,
+					requested_amount: amount,
+				});
+				Self::deposit_event(Event::<T>::AirdropStateUpdated {
+					old_state,
+					new_state,
+				});
+
+				return false;
+			}
+
+			volume.amount = claimed_amount;
+			<ClaimVolume<T>>::put(volume);
+			true
+		}
+
 		pub fn get_creditor_account() -> Result<types::AccountIdOf<T>, Error<T>> {
 			Self::try_get_creditor_account().ok_or(Error::<T>::NoCreditorAccount)
 		}
@@ -461,6 +685,27 @@ pub mod pallet {
 			Self::try_get_merkle_root().ok_or(Error::<T>::NoMerkleRoot)
 		}
 
+		pub fn do_change_merkle_root(new_root: [u8; 32], new_scheme: types::MerkleHashScheme) {
+			let old_root = Self::try_get_merkle_root();
+			let old_scheme = Self::get_merkle_hash_scheme();
+
+			MerkleRoot::<T>::put(&new_root);
+			MerkleHashingScheme::<T>::put(new_scheme);
+
+			info!(
+				"Merkle root changed from {old_root:?} to {new_root:?} with scheme {new_scheme:?} at height {bl_num:?}",
+				bl_num = utils::get_current_block_number::<T>()
+			);
+
+			Self::deposit_event(Event::<T>::MerkleRootUpdated { old_root, new_root });
+			if old_scheme != new_scheme {
+				Self::deposit_event(Event::<T>::MerkleHashSchemeUpdated {
+					old_scheme,
+					new_scheme,
+				});
+			}
+		}
+
 		/// Check weather node is set to block incoming exchange request
 		/// Return error in that case else return Ok
 		pub fn ensure_exchange_claim_switch() -> DispatchResult {
@@ -473,16 +718,70 @@ pub mod pallet {
 			}
 		}
 
+		pub fn get_airdrop_server_account(
+			account: &types::AccountIdOf<T>,
+		) -> Option<types::ServerAccountInfoOf<T>> {
+			<ServerAccounts<T>>::get(account)
+		}
+
 		/// Helper function to create similar interface like `ensure_root`
-		/// but which instead check for server key
-		pub fn ensure_root_or_server(origin: OriginFor<T>) -> DispatchResult {
-			let is_root = ensure_root(origin.clone()).is_ok();
-			let is_offchain = {
-				let signed = ensure_signed(origin);
-				signed.is_ok() && signed.ok() == Self::get_airdrop_server_account()
-			};
+		/// but which instead check for server key.
+		/// A claim of given amount is counted against the quota of server account
+		pub fn ensure_root_or_server(
+			origin: OriginFor<T>,
+			amount: types::BalanceOf<T>,
+		) -> DispatchResult {
+			if ensure_root(origin.clone()).is_ok() {
+				return Ok(());
+			}
 
-			ensure!(is_root || is_offchain, DispatchError::BadOrigin);
+			let account = ensure_signed(origin).map_err(|_| DispatchError::BadOrigin)?;
+			let mut server_info =
+				Self::get_airdrop_server_account(&account).ok_or(DispatchError::BadOrigin)?;
+
+			if let Some((max_claims, period)) = server_info.quota.claims {
+				let now = utils::get_current_block_number::<T>();
+				let period_length = match period {
+					types::QuotaPeriod::PerBlock => 1_u32.into(),
+					types::QuotaPeriod::PerDay => T::BlocksPerDay::get(),
+				};
+
+				// Start new period if current one is over
+				if now >= server_info.period_start.saturating_add(period_length) {
+					server_info.period_start = now;
+					server_info.claims_in_period = 0;
+				}
+
+				ensure!(
+					server_info.claims_in_period < max_claims,
+					Error::<T>::ServerQuotaExceeded
+				);
+				server_info.claims_in_period += 1;
+
+				if server_info.claims_in_period == max_claims {
+					Self::deposit_event(Event::<T>::ServerClaimQuotaReached {
+						account: account.clone(),
+						period,
+					});
+				}
+			}
+
+			let total_claimed = server_info
+				.total_claimed
+				.checked_add(&amount)
+				.ok_or(sp_runtime::ArithmeticError::Overflow)?;
+			if let Some(max_amount) = server_info.quota.total_amount {
+				ensure!(total_claimed <= max_amount, Error::<T>::ServerQuotaExceeded);
+
+				if total_claimed == max_amount {
+					Self::deposit_event(Event::<T>::ServerAmountQuotaReached {
+						account: account.clone(),
+					});
+				}
+			}
+			server_info.total_claimed = total_claimed;
+
+			<ServerAccounts<T>>::insert(&account, server_info);
 			Ok(())
 		}
 
@@ -493,6 +792,12 @@ pub mod pallet {
 			defi_user: bool,
 			amount: types::BalanceOf<T>,
 		) -> Result<types::SnapshotInfo<T>, DispatchError> {
+			// Snapshots not yet migrated can not be decoded
+			ensure!(
+				Self::get_storage_version() >= migrations::VESTING_CONVERSION_VERSION,
+				Error::<T>::MigrationInProgress
+			);
+
 			let ice_account =
 				Self::convert_to_account_id(ice_address.to_vec().try_into().map_err(|_| {
 					error!(
@@ -635,11 +940,16 @@ pub mod pallet {
 			proof_hashes: types::MerkleProofs<T>,
 		) -> DispatchResult {
 			let amount = types::from_balance::<T>(amount);
-			let leaf_hash = merkle::hash_leaf(icon_address, amount, defi_user);
+			let scheme = Self::get_merkle_hash_scheme();
+			let leaf_hash = merkle::hash_leaf_with_scheme(scheme, icon_address, amount, defi_user);
 			let merkle_root = Self::get_merkle_root()?;
 
-			let is_valid_proof =
-				<T as Config>::MerkelProofValidator::validate(leaf_hash, merkle_root, proof_hashes);
+			let is_valid_proof = <T as Config>::MerkelProofValidator::validate(
+				leaf_hash,
+				merkle_root,
+				proof_hashes,
+				scheme,
+			);
 			if !is_valid_proof {
 				return Err(Error::<T>::InvalidMerkleProof.into());
 			}
@@ -666,6 +976,95 @@ pub mod pallet {
 			// Now snapshot have been written, return result
 			transfer_result
 		}
+
+		/// Check weather both instant and vesting part of this icon address
+		/// have been transferred
+		pub fn is_claimed(icon_address: &types::IconAddress) -> bool {
+			Self::get_icon_snapshot_map(icon_address)
+				.map(|snapshot| snapshot.done_instant && snapshot.done_vesting)
+				.unwrap_or(false)
+		}
+
+		/// Amount that have already been transferred (instantly or as vesting schedule)
+		/// to the ice address mapped with this icon address
+		pub fn claimed_amount(icon_address: &types::IconAddress) -> types::BalanceOf<T> {
+			match Self::get_icon_snapshot_map(icon_address) {
+				Some(snapshot) if snapshot.done_instant && snapshot.done_vesting => snapshot.amount,
+				Some(snapshot) if snapshot.done_instant => snapshot.initial_transfer,
+				_ => 0_u32.into(),
+			}
+		}
+
+		/// Claim request submitted from evm side. Here the ice address is the account
+		/// mapped from evm caller, so the ice signature is not required as the caller
+		/// itself have signed the evm transaction
+		pub fn dispatch_evm_claim(
+			ice_account: types::AccountIdOf<T>,
+			icon_address: types::IconAddress,
+			message: types::RawPayload,
+			icon_signature: types::IconSignature,
+			total_amount: types::BalanceOf<T>,
+			defi_user: bool,
+			proofs: types::MerkleProofs<T>,
+		) -> DispatchResult {
+			Self::ensure_user_claim_switch()?;
+
+			// Same as user claim, blocking of claims must not be reverted
+			if !Self::register_claim_volume(total_amount) {
+				error!("evm claim request by: {icon_address:?}. Claim rate limit exceeded. Blocking new claims.");
+				return Ok(());
+			}
+
+			let ice_address: types::IceAddress = ice_account
+				.encode()
+				.try_into()
+				.map_err(|_| Error::<T>::IncompatibleAccountId)?;
+
+			Self::validate_message_payload(&message, &ice_address).map_err(|e| {
+				info!(
+					"evm claim request by: {icon_address:?}. Rejected at: validate_message_payload(). Error: {e:?}"
+				);
+				e
+			})?;
+
+			Self::validate_merkle_proof(&icon_address, total_amount, defi_user, proofs).map_err(
+				|e| {
+					info!(
+						"evm claim request by: {icon_address:?}. Rejected at: validate_merkle_proof()"
+					);
+					e
+				},
+			)?;
+
+			Self::validate_icon_address(&icon_address, &icon_signature, &message).map_err(|e| {
+				info!(
+					"evm claim request by: {icon_address:?}. Rejected at: validate_icon_address()"
+				);
+				e
+			})?;
+
+			let mut snapshot =
+				Self::insert_or_get_snapshot(&icon_address, &ice_address, defi_user, total_amount)
+					.map_err(|e| {
+						info!("evm claim request by: {icon_address:?}. Rejected at: insert_or_get_snapshot. error: {e:?}");
+						e
+					})?;
+
+			Self::ensure_claimable(&snapshot)?;
+
+			Self::validate_creditor_fund(total_amount).map_err(|e| {
+				error!("evm claim request by: {icon_address:?}. Rejected at: validate_creditor_fund(). Amount: {total_amount:?}");
+				e
+			})?;
+
+			Self::do_transfer(&mut snapshot, &icon_address).map_err(|e| {
+				error!("evm claim request by: {icon_address:?}. Failed at: do_transfer(). Reason: {e:?}. Snapshot: {snapshot:?}");
+				e
+			})?;
+
+			Self::deposit_event(Event::ClaimSuccess(icon_address));
+			Ok(())
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -715,6 +1114,7 @@ pub mod pallet {
 
 			CreditorAccount::<T>::put(&self.creditor_account);
 			MerkleRoot::<T>::put(&self.merkle_root);
+			StorageVersion::<T>::put(migrations::LATEST_VERSION);
 		}
 	}
 }
@@ -14,38 +14,123 @@ impl<T: Config> MerkelProofValidator<T> for AirdropMerkleValidator<T> {
 		leaf_hash: types::MerkleHash,
 		root_hash: types::MerkleHash,
 		proofs: types::MerkleProofs<T>,
+		scheme: types::MerkleHashScheme,
 	) -> bool {
-		let computed_root = hex::encode(proof_root(leaf_hash, proofs.to_vec()));
-		let root_hex = hex::encode(root_hash);
+		let computed_root = proof_root_with_scheme(scheme, leaf_hash, proofs.to_vec());
 
-		computed_root == root_hex
+		computed_root == root_hash
 	}
 }
 
-pub fn hash_leaf(
+/// Hashing used to construct the leaf and intermediate nodes of merkle tree
+pub trait MerkleHasher {
+	fn hash_leaf(
+		icon_address: &types::IconAddress,
+		amount: types::ServerBalance,
+		defi_user: bool,
+	) -> types::MerkleHash;
+
+	fn hash_nodes(one: types::MerkleHash, other: types::MerkleHash) -> types::MerkleHash;
+}
+
+/// Original scheme. Leaf is blake2_256 of icon address followed by
+/// decimal string of amount and "1" or "0" for defi user
+pub struct Blake2Hasher;
+
+impl MerkleHasher for Blake2Hasher {
+	fn hash_leaf(
+		icon_address: &types::IconAddress,
+		amount: types::ServerBalance,
+		defi_user: bool,
+	) -> types::MerkleHash {
+		let defi_str = if defi_user { "1" } else { "0" };
+		let mut byte_vec = icon_address.to_vec();
+		byte_vec.extend_from_slice(amount.to_string().as_bytes());
+		byte_vec.extend_from_slice(defi_str.as_bytes());
+		hashing::blake2_256(&byte_vec)
+	}
+
+	fn hash_nodes(one: types::MerkleHash, other: types::MerkleHash) -> types::MerkleHash {
+		let sorted = sort_array(one, other, 0_usize);
+		hashing::blake2_256(&sorted)
+	}
+}
+
+/// Solidity compatible scheme. Leaf is
+/// `keccak256(abi.encodePacked(address iconAddress, uint256 amount, bool defiUser))`
+/// and nodes are hashed as sorted pair, same as openzeppelin `MerkleProof`
+pub struct KeccakHasher;
+
+impl MerkleHasher for KeccakHasher {
+	fn hash_leaf(
+		icon_address: &types::IconAddress,
+		amount: types::ServerBalance,
+		defi_user: bool,
+	) -> types::MerkleHash {
+		let mut amount_word = [0u8; 32];
+		amount_word[16..].copy_from_slice(&amount.to_be_bytes());
+
+		let mut byte_vec = icon_address.to_vec();
+		byte_vec.extend_from_slice(&amount_word);
+		byte_vec.push(defi_user as u8);
+		hashing::keccak_256(&byte_vec)
+	}
+
+	fn hash_nodes(one: types::MerkleHash, other: types::MerkleHash) -> types::MerkleHash {
+		let sorted = sort_array(one, other, 0_usize);
+		hashing::keccak_256(&sorted)
+	}
+}
+
+pub fn hash_leaf_with_scheme(
+	scheme: types::MerkleHashScheme,
 	icon_address: &types::IconAddress,
 	amount: types::ServerBalance,
 	defi_user: bool,
-) -> [u8; 32] {
-	let defi_str = if defi_user { "1" } else { "0" };
-	let mut byte_vec = icon_address.to_vec();
-	byte_vec.extend_from_slice(amount.to_string().as_bytes());
-	byte_vec.extend_from_slice(defi_str.as_bytes());
-	hashing::blake2_256(&byte_vec)
+) -> types::MerkleHash {
+	match scheme {
+		types::MerkleHashScheme::Blake2 => Blake2Hasher::hash_leaf(icon_address, amount, defi_user),
+		types::MerkleHashScheme::Keccak => KeccakHasher::hash_leaf(icon_address, amount, defi_user),
+	}
 }
 
-pub fn proof_root(leaf_hash: types::MerkleHash, proofs: Vec<types::MerkleHash>) -> [u8; 32] {
+pub fn proof_root_with_scheme(
+	scheme: types::MerkleHashScheme,
+	leaf_hash: types::MerkleHash,
+	proofs: Vec<types::MerkleHash>,
+) -> types::MerkleHash {
+	match scheme {
+		types::MerkleHashScheme::Blake2 => proof_root_with::<Blake2Hasher>(leaf_hash, proofs),
+		types::MerkleHashScheme::Keccak => proof_root_with::<KeccakHasher>(leaf_hash, proofs),
+	}
+}
+
+pub fn proof_root_with<H: MerkleHasher>(
+	leaf_hash: types::MerkleHash,
+	proofs: Vec<types::MerkleHash>,
+) -> types::MerkleHash {
 	let mut one = leaf_hash;
 	for proof in proofs {
-		one = create_hash(one, proof);
+		one = H::hash_nodes(one, proof);
 	}
 
 	one
 }
 
+pub fn hash_leaf(
+	icon_address: &types::IconAddress,
+	amount: types::ServerBalance,
+	defi_user: bool,
+) -> [u8; 32] {
+	Blake2Hasher::hash_leaf(icon_address, amount, defi_user)
+}
+
+pub fn proof_root(leaf_hash: types::MerkleHash, proofs: Vec<types::MerkleHash>) -> [u8; 32] {
+	proof_root_with::<Blake2Hasher>(leaf_hash, proofs)
+}
+
 pub fn create_hash(one: types::MerkleHash, other: types::MerkleHash) -> [u8; 32] {
-	let sorted = sort_array(one, other, 0_usize);
-	hashing::blake2_256(&sorted)
+	Blake2Hasher::hash_nodes(one, other)
 }
 
 pub fn sort_array(one: types::MerkleHash, other: types::MerkleHash, pos: usize) -> Vec<u8> {
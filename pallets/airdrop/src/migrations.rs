@@ -0,0 +1,129 @@
+use crate::{
+	info, types, Config, IconSnapshotMap, Pallet, ServerAccounts, StorageVersion,
+	VestingMigrationCursor,
+};
+use codec::{Decode, DecodeAll, Encode};
+use frame_support::{
+	storage::{migration::take_storage_value, unhashed, StoragePrefixedMap},
+	traits::{Get, PalletInfoAccess},
+	weights::Weight,
+};
+
+/// Storage version since which server accounts are kept in `ServerAccounts` map
+pub const SERVER_ACCOUNTS_VERSION: u32 = 2;
+
+/// Storage version since which snapshot record vesting conversion
+pub const VESTING_CONVERSION_VERSION: u32 = 3;
+
+/// Maximum number of snapshots translated in a single block
+/// while migrating to `VESTING_CONVERSION_VERSION`
+pub const VESTING_CONVERSION_BATCH: u32 = 500;
+
+/// Storage version of freshly built chain
+pub const LATEST_VERSION: u32 = VESTING_CONVERSION_VERSION;
+
+/// Move single `ServerAccount` value into `ServerAccounts` map.
+/// Previously authorized account is kept authorized without any quota
+pub fn migrate_to_server_accounts<T: Config>() -> Weight {
+	let db_weight = T::DbWeight::get();
+	if <StorageVersion<T>>::get() >= SERVER_ACCOUNTS_VERSION {
+		return db_weight.reads(1);
+	}
+
+	let old_account = take_storage_value::<types::AccountIdOf<T>>(
+		<Pallet<T> as PalletInfoAccess>::name().as_bytes(),
+		b"ServerAccount",
+		&[],
+	);
+	if let Some(account) = old_account.clone() {
+		<ServerAccounts<T>>::insert(
+			&account,
+			types::ServerAccountInfo::new(Default::default(), Default::default()),
+		);
+	}
+	<StorageVersion<T>>::put(SERVER_ACCOUNTS_VERSION);
+
+	info!("Migrated server account {old_account:?} to ServerAccounts map");
+
+	db_weight.reads_writes(2, 4)
+}
+
+/// Snapshot as stored before `VESTING_CONVERSION_VERSION`
+#[derive(Decode)]
+struct SnapshotInfoV2<T: Config> {
+	ice_address: types::AccountIdOf<T>,
+	amount: types::BalanceOf<T>,
+	defi_user: bool,
+	done_instant: bool,
+	done_vesting: bool,
+	vesting_block_number: Option<types::BlockNumberOf<T>>,
+	instant_block_number: Option<types::BlockNumberOf<T>>,
+	initial_transfer: types::BalanceOf<T>,
+}
+
+/// Add empty `vesting_conversion` to existing snapshots.
+/// This is a multi-block migration called from `on_initialize`. At most
+/// `VESTING_CONVERSION_BATCH` snapshots are translated per block, continuing from
+/// the raw key stored in `VestingMigrationCursor`. Storage version is only bumped
+/// once all snapshots are translated, new claims are rejected until then
+pub fn migrate_to_vesting_conversion<T: Config>() -> Weight {
+	let db_weight = T::DbWeight::get();
+	if <StorageVersion<T>>::get() >= VESTING_CONVERSION_VERSION {
+		return db_weight.reads(1);
+	}
+
+	let prefix = <IconSnapshotMap<T>>::final_prefix();
+	let mut cursor = <VestingMigrationCursor<T>>::get()
+		.map(|cursor| cursor.into_inner())
+		.unwrap_or_else(|| prefix.to_vec());
+
+	let mut translated = 0_u64;
+	let mut visited = 0_u32;
+	let finished = loop {
+		if visited >= VESTING_CONVERSION_BATCH {
+			break false;
+		}
+
+		let key = match sp_io::storage::next_key(&cursor) {
+			Some(key) if key.starts_with(&prefix) => key,
+			_ => break true,
+		};
+		visited += 1;
+
+		// Snapshots inserted with new format will fail to decode as old one
+		let old = unhashed::get_raw(&key)
+			.and_then(|raw| SnapshotInfoV2::<T>::decode_all(&mut &raw[..]).ok());
+		if let Some(old) = old {
+			let new = types::SnapshotInfo::<T> {
+				ice_address: old.ice_address,
+				amount: old.amount,
+				defi_user: old.defi_user,
+				done_instant: old.done_instant,
+				done_vesting: old.done_vesting,
+				vesting_block_number: old.vesting_block_number,
+				instant_block_number: old.instant_block_number,
+				initial_transfer: old.initial_transfer,
+				vesting_conversion: None,
+			};
+			unhashed::put_raw(&key, &new.encode());
+			translated += 1;
+		}
+
+		cursor = key;
+	};
+
+	if finished {
+		<VestingMigrationCursor<T>>::kill();
+		<StorageVersion<T>>::put(VESTING_CONVERSION_VERSION);
+		info!("Finished migrating snapshots to include vesting conversion");
+	} else {
+		// Raw key of `IconSnapshotMap` always fits in the cursor bound
+		<VestingMigrationCursor<T>>::put(
+			types::MigrationCursor::try_from(cursor).unwrap_or_default(),
+		);
+	}
+
+	info!("Migrated {translated} snapshots to include vesting conversion");
+
+	db_weight.reads_writes(u64::from(visited) + 2, translated + 1)
+}
@@ -0,0 +1,253 @@
+use crate as airdrop;
+use airdrop::{types, weights::WeightInfo, Pallet as AirdropModule};
+use fp_evm::{
+	ExitError, ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle,
+	PrecompileOutput, PrecompileResult,
+};
+use frame_support::traits::Get;
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use sp_core::{H160, U256};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Size of a single abi encoded word
+const WORD_SIZE: usize = 32;
+
+/// Function selectors i.e first 4 bytes of keccak256 of function signature
+pub mod selector {
+	/// isClaimed(address)
+	pub const IS_CLAIMED: [u8; 4] = [0x8c, 0xc0, 0x80, 0x25];
+	/// claimedAmount(address)
+	pub const CLAIMED_AMOUNT: [u8; 4] = [0x04, 0xe8, 0x69, 0x03];
+	/// linkedIconAddress(address)
+	pub const LINKED_ICON_ADDRESS: [u8; 4] = [0x58, 0x58, 0xb8, 0x14];
+	/// claim(address,bytes,bytes,uint256,bool,bytes32[])
+	pub const CLAIM: [u8; 4] = [0x9d, 0xb2, 0x40, 0x91];
+}
+
+/// Precompile that expose airdrop claim status of icon address
+/// and allow evm caller to claim the airdrop into the account mapped to caller
+///
+/// Read methods:
+/// - `isClaimed(address iconAddress) returns (bool)`
+/// - `claimedAmount(address iconAddress) returns (uint256)`
+/// - `linkedIconAddress(address evmAddress) returns (address)`
+///
+/// Write methods:
+/// - `claim(address iconAddress, bytes message, bytes iconSignature,
+///    uint256 amount, bool defiUser, bytes32[] proofs)`
+pub struct AirdropPrecompile<R>(PhantomData<R>);
+
+impl<R> Precompile for AirdropPrecompile<R>
+where
+	R: airdrop::Config + pallet_evm::Config,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let input = handle.input().to_vec();
+		if input.len() < 4 {
+			return Err(revert("airdrop: invalid input"));
+		}
+
+		let (method, args) = input.split_at(4);
+		match [method[0], method[1], method[2], method[3]] {
+			selector::IS_CLAIMED => Self::is_claimed(handle, args),
+			selector::CLAIMED_AMOUNT => Self::claimed_amount(handle, args),
+			selector::LINKED_ICON_ADDRESS => Self::linked_icon_address(handle, args),
+			selector::CLAIM => Self::claim(handle, args),
+			_ => Err(revert("airdrop: unknown selector")),
+		}
+	}
+}
+
+impl<R> AirdropPrecompile<R>
+where
+	R: airdrop::Config + pallet_evm::Config,
+{
+	fn is_claimed(handle: &mut impl PrecompileHandle, args: &[u8]) -> PrecompileResult {
+		Self::record_reads(handle, 1)?;
+		let icon_address = read_icon_address(args, 0)?;
+
+		let is_claimed = AirdropModule::<R>::is_claimed(&icon_address);
+		Ok(succeed(encode_bool(is_claimed)))
+	}
+
+	fn claimed_amount(handle: &mut impl PrecompileHandle, args: &[u8]) -> PrecompileResult {
+		Self::record_reads(handle, 1)?;
+		let icon_address = read_icon_address(args, 0)?;
+
+		let amount = types::from_balance::<R>(AirdropModule::<R>::claimed_amount(&icon_address));
+		Ok(succeed(encode_u256(U256::from(amount))))
+	}
+
+	fn linked_icon_address(handle: &mut impl PrecompileHandle, args: &[u8]) -> PrecompileResult {
+		Self::record_reads(handle, 1)?;
+		let evm_address = read_address(args, 0)?;
+		let ice_account = <R as pallet_evm::Config>::AddressMapping::into_account_id(evm_address);
+
+		let icon_address =
+			AirdropModule::<R>::get_ice_to_icon_map(&ice_account).unwrap_or_default();
+		Ok(succeed(encode_address(H160::from(icon_address))))
+	}
+
+	fn claim(handle: &mut impl PrecompileHandle, args: &[u8]) -> PrecompileResult {
+		if handle.is_static() {
+			return Err(revert("airdrop: claim can not be called in static context"));
+		}
+		if !handle.context().apparent_value.is_zero() {
+			return Err(revert("airdrop: claim is not payable"));
+		}
+
+		let weight = <R as airdrop::Config>::AirdropWeightInfo::dispatch_user_claim();
+		handle.record_cost(<R as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			weight,
+		))?;
+
+		let icon_address = read_icon_address(args, 0)?;
+		let message: types::RawPayload = read_bytes(args, 1)?
+			.try_into()
+			.map_err(|_| revert("airdrop: invalid message length"))?;
+		let icon_signature: types::IconSignature = read_bytes(args, 2)?
+			.try_into()
+			.map_err(|_| revert("airdrop: invalid icon signature length"))?;
+		let amount = read_u256(args, 3)?;
+		let defi_user = read_bool(args, 4)?;
+		let proofs = AirdropModule::<R>::get_bounded_proofs(read_bytes32_array(args, 5)?)
+			.map_err(|_| revert("airdrop: proof too large"))?;
+
+		if amount > U256::from(u128::MAX) {
+			return Err(revert("airdrop: amount overflow"));
+		}
+		let amount = types::to_balance::<R>(amount.low_u128());
+
+		let caller = handle.context().caller;
+		let ice_account = <R as pallet_evm::Config>::AddressMapping::into_account_id(caller);
+
+		frame_support::storage::with_storage_layer(|| {
+			AirdropModule::<R>::dispatch_evm_claim(
+				ice_account,
+				icon_address,
+				message,
+				icon_signature,
+				amount,
+				defi_user,
+				proofs,
+			)
+		})
+		.map_err(|e| revert(<&'static str>::from(e)))?;
+
+		// Claim exceeding the rate limit is not an error to the pallet so that
+		// blocking of claims is kept, but caller still have to see it failed
+		if AirdropModule::<R>::get_airdrop_state().block_claim_request {
+			return Err(revert("airdrop: claim rate limit exceeded"));
+		}
+
+		Ok(succeed(Vec::new()))
+	}
+
+	fn record_reads(handle: &mut impl PrecompileHandle, reads: u64) -> Result<(), ExitError> {
+		let weight = <R as frame_system::Config>::DbWeight::get().reads(reads);
+		handle.record_cost(<R as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			weight,
+		))
+	}
+}
+
+fn succeed(output: Vec<u8>) -> PrecompileOutput {
+	PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output,
+	}
+}
+
+fn revert(message: &str) -> PrecompileFailure {
+	PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: message.as_bytes().to_vec(),
+	}
+}
+
+fn read_word(input: &[u8], offset: usize) -> Result<&[u8], PrecompileFailure> {
+	let end = offset
+		.checked_add(WORD_SIZE)
+		.ok_or_else(|| revert("airdrop: invalid input"))?;
+	input
+		.get(offset..end)
+		.ok_or_else(|| revert("airdrop: input too short"))
+}
+
+fn read_offset(input: &[u8], offset: usize) -> Result<usize, PrecompileFailure> {
+	let value = U256::from_big_endian(read_word(input, offset)?);
+	if value > U256::from(u32::MAX) {
+		return Err(revert("airdrop: offset out of bounds"));
+	}
+	Ok(value.low_u32() as usize)
+}
+
+fn read_address(args: &[u8], index: usize) -> Result<H160, PrecompileFailure> {
+	let word = read_word(args, index * WORD_SIZE)?;
+	Ok(H160::from_slice(&word[12..]))
+}
+
+fn read_icon_address(args: &[u8], index: usize) -> Result<types::IconAddress, PrecompileFailure> {
+	Ok(read_address(args, index)?.to_fixed_bytes())
+}
+
+fn read_u256(args: &[u8], index: usize) -> Result<U256, PrecompileFailure> {
+	Ok(U256::from_big_endian(read_word(args, index * WORD_SIZE)?))
+}
+
+fn read_bool(args: &[u8], index: usize) -> Result<bool, PrecompileFailure> {
+	match read_u256(args, index)? {
+		value if value.is_zero() => Ok(false),
+		value if value == U256::one() => Ok(true),
+		_ => Err(revert("airdrop: invalid bool")),
+	}
+}
+
+fn read_bytes(args: &[u8], index: usize) -> Result<Vec<u8>, PrecompileFailure> {
+	let offset = read_offset(args, index * WORD_SIZE)?;
+	let length = read_offset(args, offset)?;
+	let start = offset
+		.checked_add(WORD_SIZE)
+		.ok_or_else(|| revert("airdrop: invalid input"))?;
+	let end = start
+		.checked_add(length)
+		.ok_or_else(|| revert("airdrop: invalid input"))?;
+	args.get(start..end)
+		.map(|bytes| bytes.to_vec())
+		.ok_or_else(|| revert("airdrop: input too short"))
+}
+
+fn read_bytes32_array(
+	args: &[u8],
+	index: usize,
+) -> Result<Vec<types::MerkleHash>, PrecompileFailure> {
+	let offset = read_offset(args, index * WORD_SIZE)?;
+	let length = read_offset(args, offset)?;
+	(0..length)
+		.map(|i| {
+			let mut hash = [0u8; WORD_SIZE];
+			let position = WORD_SIZE
+				.checked_mul(i + 1)
+				.and_then(|position| position.checked_add(offset))
+				.ok_or_else(|| revert("airdrop: invalid input"))?;
+			hash.copy_from_slice(read_word(args, position)?);
+			Ok(hash)
+		})
+		.collect()
+}
+
+fn encode_u256(value: U256) -> Vec<u8> {
+	let mut output = sp_std::vec![0u8; WORD_SIZE];
+	value.to_big_endian(&mut output);
+	output
+}
+
+fn encode_bool(value: bool) -> Vec<u8> {
+	encode_u256(if value { U256::one() } else { U256::zero() })
+}
+
+fn encode_address(address: H160) -> Vec<u8> {
+	let mut output = sp_std::vec![0u8; WORD_SIZE];
+	output[12..].copy_from_slice(address.as_bytes());
+	output
+}
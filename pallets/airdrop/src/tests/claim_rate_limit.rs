@@ -0,0 +1,240 @@
+use super::prelude::*;
+use crate::{tests::UserClaimTestCase, Config};
+use frame_support::traits::Currency;
+
+fn dispatch_claim(server_account: types::AccountIdOf<Test>, case: &UserClaimTestCase) {
+	assert_ok!(AirdropModule::dispatch_user_claim(
+		RuntimeOrigin::signed(server_account),
+		case.icon_address,
+		case.ice_address,
+		case.message,
+		case.icon_signature,
+		case.ice_signature,
+		case.amount,
+		case.defi_user,
+		case.merkle_proofs.clone(),
+	));
+}
+
+#[test]
+fn set_claim_rate_limit() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		let limit = types::ClaimRateLimit {
+			max_amount: 1_000,
+			period: 10,
+		};
+
+		assert_noop!(
+			AirdropModule::set_claim_rate_limit(
+				RuntimeOrigin::signed(samples::ACCOUNT_ID[0]),
+				Some(limit.clone())
+			),
+			PalletError::DeniedOperation
+		);
+
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(limit.clone())
+		));
+		assert_eq!(Some(limit.clone()), AirdropModule::get_claim_rate_limit());
+		assert_eq!(
+			get_last_event(),
+			Some(
+				PalletEvent::ClaimRateLimitChanged {
+					old_limit: None,
+					new_limit: Some(limit),
+				}
+				.into()
+			)
+		);
+	});
+}
+
+#[test]
+fn claim_within_limit() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		set_creditor_balance(10_000_0000);
+		let server_account = samples::ACCOUNT_ID[0];
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			server_account,
+			Default::default(),
+		));
+
+		let mut case = UserClaimTestCase::default();
+		case.amount = 12_017_332_u64.into();
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(types::ClaimRateLimit {
+				max_amount: case.amount,
+				period: 10,
+			})
+		));
+
+		dispatch_claim(server_account, &case);
+
+		let ice_account = AirdropModule::convert_to_account_id(case.ice_address).unwrap();
+		assert_eq!(
+			<Test as Config>::Currency::total_balance(&ice_account),
+			case.amount
+		);
+		assert_eq!(
+			types::ClaimVolume {
+				window_start: 2,
+				amount: case.amount,
+			},
+			AirdropModule::get_claim_volume()
+		);
+		assert_eq!(
+			false,
+			AirdropModule::get_airdrop_state().block_claim_request
+		);
+	});
+}
+
+#[test]
+fn exceeding_limit_block_claims() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		set_creditor_balance(10_000_0000);
+		let server_account = samples::ACCOUNT_ID[0];
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			server_account,
+			Default::default(),
+		));
+
+		let mut case = UserClaimTestCase::default();
+		case.amount = 12_017_332_u64.into();
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(types::ClaimRateLimit {
+				max_amount: case.amount - 1,
+				period: 10,
+			})
+		));
+
+		// Claim is not paid but claims are blocked from now
+		dispatch_claim(server_account, &case);
+
+		let ice_account = AirdropModule::convert_to_account_id(case.ice_address).unwrap();
+		assert_eq!(<Test as Config>::Currency::total_balance(&ice_account), 0);
+		assert_eq!(true, AirdropModule::get_airdrop_state().block_claim_request);
+		assert!(<frame_system::Pallet<Test>>::events()
+			.iter()
+			.any(|record| record.event
+				== PalletEvent::ClaimRateLimitExceeded {
+					window_start: 2,
+					claimed_amount: 0,
+					requested_amount: case.amount,
+				}
+				.into()));
+		assert_eq!(
+			get_last_event(),
+			Some(
+				PalletEvent::AirdropStateUpdated {
+					old_state: types::AirdropState::default(),
+					new_state: types::AirdropState {
+						block_claim_request: true,
+						..Default::default()
+					},
+				}
+				.into()
+			)
+		);
+
+		assert_noop!(
+			AirdropModule::dispatch_user_claim(
+				RuntimeOrigin::signed(server_account),
+				case.icon_address,
+				case.ice_address,
+				case.message,
+				case.icon_signature,
+				case.ice_signature,
+				case.amount,
+				case.defi_user,
+				case.merkle_proofs.clone(),
+			),
+			PalletError::NewClaimRequestBlocked
+		);
+
+		// Only governance can re-open the claims
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(types::ClaimRateLimit {
+				max_amount: case.amount,
+				period: 10,
+			})
+		));
+		assert_noop!(
+			AirdropModule::dispatch_user_claim(
+				RuntimeOrigin::signed(server_account),
+				case.icon_address,
+				case.ice_address,
+				case.message,
+				case.icon_signature,
+				case.ice_signature,
+				case.amount,
+				case.defi_user,
+				case.merkle_proofs.clone(),
+			),
+			PalletError::NewClaimRequestBlocked
+		);
+		assert_ok!(AirdropModule::update_airdrop_state(
+			RuntimeOrigin::root(),
+			types::AirdropState::default()
+		));
+
+		dispatch_claim(server_account, &case);
+		assert_eq!(
+			<Test as Config>::Currency::total_balance(&ice_account),
+			case.amount
+		);
+	});
+}
+
+#[test]
+fn rate_limit_window_resets() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(types::ClaimRateLimit {
+				max_amount: 100,
+				period: 10,
+			})
+		));
+
+		assert_eq!(true, AirdropModule::register_claim_volume(60));
+		assert_eq!(true, AirdropModule::register_claim_volume(40));
+		assert_eq!(false, AirdropModule::register_claim_volume(1));
+
+		// Re-open claims blocked above
+		assert_ok!(AirdropModule::update_airdrop_state(
+			RuntimeOrigin::root(),
+			types::AirdropState::default()
+		));
+
+		run_to_block(12);
+		assert_eq!(true, AirdropModule::register_claim_volume(100));
+		assert_eq!(
+			types::ClaimVolume {
+				window_start: 12,
+				amount: 100,
+			},
+			AirdropModule::get_claim_volume()
+		);
+
+		assert_eq!(false, AirdropModule::register_claim_volume(1));
+		assert_eq!(true, AirdropModule::get_airdrop_state().block_claim_request);
+		assert_eq!(
+			types::ClaimVolume {
+				window_start: 12,
+				amount: 100,
+			},
+			AirdropModule::get_claim_volume()
+		);
+	});
+}
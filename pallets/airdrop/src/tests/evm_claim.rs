@@ -0,0 +1,148 @@
+use super::prelude::*;
+use crate::{tests::UserClaimTestCase, AirdropChainState, Config};
+use frame_support::traits::Currency;
+
+#[test]
+fn claim_success() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+
+		let case = UserClaimTestCase::default();
+		let ice_account = AirdropModule::convert_to_account_id(case.ice_address).unwrap();
+
+		assert_eq!(AirdropModule::is_claimed(&case.icon_address), false);
+		assert_eq!(AirdropModule::claimed_amount(&case.icon_address), 0);
+
+		assert_ok!(AirdropModule::dispatch_evm_claim(
+			ice_account.clone(),
+			case.icon_address,
+			case.message,
+			case.icon_signature,
+			case.amount,
+			case.defi_user,
+			case.merkle_proofs,
+		));
+
+		let total_balance = <Test as Config>::Currency::total_balance(&ice_account);
+		let mapped_icon_wallet = AirdropModule::get_ice_to_icon_map(&ice_account);
+
+		assert_eq!(total_balance, case.amount);
+		assert_eq!(mapped_icon_wallet, Some(case.icon_address));
+		assert_eq!(AirdropModule::is_claimed(&case.icon_address), true);
+		assert_eq!(
+			AirdropModule::claimed_amount(&case.icon_address),
+			case.amount
+		);
+	});
+}
+
+#[test]
+fn claimed_amount_of_partial_claim() {
+	minimal_test_ext().execute_with(|| {
+		let case = UserClaimTestCase::default();
+
+		let mut snapshot = types::SnapshotInfo::default();
+		snapshot.amount = 1_000_u32.into();
+		snapshot.initial_transfer = 400_u32.into();
+		snapshot.done_instant = true;
+		snapshot.done_vesting = false;
+		pallet_airdrop::IconSnapshotMap::<Test>::insert(&case.icon_address, snapshot);
+
+		assert_eq!(AirdropModule::is_claimed(&case.icon_address), false);
+		assert_eq!(AirdropModule::claimed_amount(&case.icon_address), 400);
+	});
+}
+
+#[test]
+fn mismatched_caller() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+
+		let case = UserClaimTestCase::default();
+
+		assert_err!(
+			AirdropModule::dispatch_evm_claim(
+				samples::ACCOUNT_ID[0],
+				case.icon_address,
+				case.message,
+				case.icon_signature,
+				case.amount,
+				case.defi_user,
+				case.merkle_proofs,
+			),
+			PalletError::InvalidMessagePayload
+		);
+	});
+}
+
+#[test]
+fn respect_claim_switch() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+
+		<AirdropChainState<Test>>::put(types::AirdropState {
+			block_claim_request: true,
+			block_exchange_request: false,
+		});
+
+		let case = UserClaimTestCase::default();
+		let ice_account = AirdropModule::convert_to_account_id(case.ice_address).unwrap();
+
+		assert_err!(
+			AirdropModule::dispatch_evm_claim(
+				ice_account,
+				case.icon_address,
+				case.message,
+				case.icon_signature,
+				case.amount,
+				case.defi_user,
+				case.merkle_proofs,
+			),
+			PalletError::NewClaimRequestBlocked
+		);
+	});
+}
+
+#[test]
+fn respect_claim_rate_limit() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+
+		let case = UserClaimTestCase::default();
+		let ice_account = AirdropModule::convert_to_account_id(case.ice_address).unwrap();
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(types::ClaimRateLimit {
+				max_amount: case.amount - 1,
+				period: 10,
+			})
+		));
+
+		// Claim is not paid but claims are blocked from now
+		assert_ok!(AirdropModule::dispatch_evm_claim(
+			ice_account.clone(),
+			case.icon_address,
+			case.message,
+			case.icon_signature,
+			case.amount,
+			case.defi_user,
+			case.merkle_proofs.clone(),
+		));
+		assert_eq!(AirdropModule::is_claimed(&case.icon_address), false);
+		assert_eq!(<Test as Config>::Currency::total_balance(&ice_account), 0);
+		assert_eq!(true, AirdropModule::get_airdrop_state().block_claim_request);
+
+		assert_err!(
+			AirdropModule::dispatch_evm_claim(
+				ice_account,
+				case.icon_address,
+				case.message,
+				case.icon_signature,
+				case.amount,
+				case.defi_user,
+				case.merkle_proofs,
+			),
+			PalletError::NewClaimRequestBlocked
+		);
+	});
+}
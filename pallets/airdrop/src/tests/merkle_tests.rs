@@ -1,4 +1,7 @@
-use crate::merkle::{hash_leaf, proof_root, sort_array};
+use crate::merkle::{
+	hash_leaf, hash_leaf_with_scheme, proof_root, proof_root_with_scheme, sort_array,
+};
+use crate::types::MerkleHashScheme;
 use crate::utils;
 use hex_literal;
 
@@ -155,6 +158,73 @@ fn fails_invalid_proof() {
 	assert_ne!(root, hex::encode(proof_root));
 }
 
+#[test]
+fn test_keccak_hash_leaf() {
+	let cases = [
+		(
+			"a99344ea068864f8af6cbcf89328d6eb3d7e8c9c",
+			0,
+			true,
+			"ee748331ad89e8e6da0a7ced416d6d9a02ff4ee71f1dab3590421ea30ccd8b91",
+		),
+		(
+			"ee1448f0867b90e6589289a4b9c06ac4516a75a9",
+			5_000_000_000_000_000_000,
+			false,
+			"bb87f5c17ec9fade4a9d78b294d5b6680dcc4fb37fc0222d7ad82e295ea6cbb7",
+		),
+	];
+	for (icon_addr, amount, defi_user, expected) in cases {
+		let icon_addr = utils::hex_as_byte_array(icon_addr).unwrap();
+		let result = hash_leaf_with_scheme(MerkleHashScheme::Keccak, &icon_addr, amount, defi_user);
+		assert_eq!(expected, hex::encode(result));
+	}
+}
+
+#[test]
+fn test_blake2_scheme_is_default() {
+	let icon_addr: [u8; 20] = hex_literal::hex!("a99344ea068864f8af6cbcf89328d6eb3d7e8c9c");
+	assert_eq!(
+		hash_leaf(&icon_addr, 0, true),
+		hash_leaf_with_scheme(MerkleHashScheme::default(), &icon_addr, 0, true)
+	);
+}
+
+#[test]
+fn test_keccak_verify_proof() {
+	let root = "dc2bf746ae198cb802e634369be8ea14a2c796f2d2842d3b798b4fb0fe6c14c9";
+	let cases = [
+		(
+			"ee748331ad89e8e6da0a7ced416d6d9a02ff4ee71f1dab3590421ea30ccd8b91",
+			[
+				"b2d9b218a0746dbfb7f261036693d0021dae1bc272fc82d2f6c76fb36b865bb9",
+				"bdbe6ee5f7e2a91f745bb572e582b2ffca3b02eb4bdf59e53949e526959bcf0d",
+			],
+		),
+		(
+			"fe1f785859036b70db9b9226d01ccb38644cdbb5a9e5e6052468d85f920d5bbd",
+			[
+				"bb87f5c17ec9fade4a9d78b294d5b6680dcc4fb37fc0222d7ad82e295ea6cbb7",
+				"fbd536380a8a2230de11c2b19f3c9cd3cc3d35bcf60e55cdd63b3fdf66a4062e",
+			],
+		),
+	];
+	for (leaf, proofs) in cases {
+		let leaf_hash = utils::hex_as_byte_array(leaf).unwrap();
+		let proofs = proofs
+			.into_iter()
+			.map(|h| utils::hex_as_byte_array(h).unwrap())
+			.collect::<Vec<[u8; 32]>>();
+
+		let keccak_root =
+			proof_root_with_scheme(MerkleHashScheme::Keccak, leaf_hash, proofs.clone());
+		assert_eq!(root, hex::encode(keccak_root));
+
+		let blake2_root = proof_root_with_scheme(MerkleHashScheme::Blake2, leaf_hash, proofs);
+		assert_ne!(root, hex::encode(blake2_root));
+	}
+}
+
 #[test]
 fn test_sort_array() {
 	let arr1 = [0u8; 32];
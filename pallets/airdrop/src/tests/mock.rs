@@ -1,10 +1,15 @@
 use crate::{self as pallet_airdrop, types};
 use core::marker::PhantomData;
 
-use frame_support::{parameter_types, traits::ConstU32};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64},
+	weights::Weight,
+};
 use frame_system as system;
 use pallet_balances;
-use sp_core::H256;
+use pallet_evm::AddressMapping;
+use sp_core::{sr25519, H160, H256, U256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
@@ -16,6 +21,36 @@ type Balance = u128;
 type Index = u64;
 type BlockNumber = u64;
 
+pub const MOCK_BLOCK_TIME: u64 = 6_000;
+pub const MOCK_UNIX_TIME: u64 = 1_672_531_200_000;
+
+pub struct MockUnixTime;
+
+impl frame_support::traits::UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(MOCK_UNIX_TIME)
+	}
+}
+
+/// Evm address mapped to the ice address of the sample claim
+pub fn evm_claimer() -> H160 {
+	H160::repeat_byte(0xcc)
+}
+
+pub struct MockAddressMapping;
+
+impl AddressMapping<sr25519::Public> for MockAddressMapping {
+	fn into_account_id(address: H160) -> sr25519::Public {
+		if address == evm_claimer() {
+			return sr25519::Public(super::samples::VALID_ICE_ADDRESS);
+		}
+
+		let mut account = [0u8; 32];
+		account[12..].copy_from_slice(address.as_bytes());
+		sr25519::Public(account)
+	}
+}
+
 pub struct TestValidator<T>(PhantomData<T>);
 
 impl types::MerkelProofValidator<Test> for TestValidator<Test> {
@@ -23,6 +58,7 @@ impl types::MerkelProofValidator<Test> for TestValidator<Test> {
 		_root_hash: types::MerkleHash,
 		_leaf_hash: types::MerkleHash,
 		_proofs: types::MerkleProofs<Test>,
+		_scheme: types::MerkleHashScheme,
 	) -> bool {
 		return true;
 	}
@@ -38,6 +74,8 @@ frame_support::construct_runtime!(
 		AirdropModule: pallet_airdrop::{Pallet, Call, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Vesting: pallet_vesting::{Pallet, Call, Storage, Event<T>, Config<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		EVM: pallet_evm::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -86,12 +124,16 @@ impl pallet_airdrop::Config for Test {
 	type BalanceTypeConversion = sp_runtime::traits::ConvertInto;
 	type MerkelProofValidator = TestValidator<Test>;
 	type MaxProofSize = ConstU32<10>;
+	type MaxServerAccounts = ConstU32<3>;
+	type BlocksPerDay = ConstU64<20>;
+	type UnixTime = MockUnixTime;
+	type ExpectedBlockTime = ConstU64<MOCK_BLOCK_TIME>;
 
 	const VESTING_TERMS: types::VestingTerms = {
 		types::VestingTerms {
 			defi_instant_percentage: 40,
 			non_defi_instant_percentage: 30,
-			vesting_period: 5_256_000,
+			vesting_period: types::VestingPeriod::Duration(5_256_000 * MOCK_BLOCK_TIME),
 		}
 	};
 }
@@ -117,6 +159,37 @@ impl pallet_vesting::Config for Test {
 	const MAX_VESTING_SCHEDULES: u32 = 10;
 }
 
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub WeightPerGas: Weight = Weight::from_ref_time(20_000);
+	pub BlockGasLimit: U256 = U256::from(u64::MAX);
+}
+
+impl pallet_evm::Config for Test {
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type CallOrigin = pallet_evm::EnsureAddressRoot<Self::AccountId>;
+	type WithdrawOrigin = pallet_evm::EnsureAddressNever<Self::AccountId>;
+	type AddressMapping = MockAddressMapping;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type ChainId = ();
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = ();
+	type FindAuthor = ();
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	system::GenesisConfig::default()
 		.build_storage::<Test>()
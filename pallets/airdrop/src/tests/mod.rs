@@ -1,6 +1,9 @@
+mod claim_rate_limit;
+mod evm_claim;
 mod exchange_claim;
 mod merkle_tests;
 pub mod mock;
+mod precompile;
 mod signature_validation;
 mod user_claim;
 mod utility_functions;
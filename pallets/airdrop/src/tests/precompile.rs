@@ -0,0 +1,302 @@
+use super::prelude::*;
+use crate::{
+	precompile::{selector, AirdropPrecompile},
+	tests::UserClaimTestCase,
+};
+use fp_evm::{
+	Context, ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileOutput,
+	PrecompileResult,
+};
+use mock::evm_claimer;
+use pallet_evm_test_vector_support::MockHandle;
+use sp_core::{H160, U256};
+
+fn word(value: U256) -> Vec<u8> {
+	let mut word = vec![0u8; 32];
+	value.to_big_endian(&mut word);
+	word
+}
+
+fn address_word(address: [u8; 20]) -> Vec<u8> {
+	let mut word = vec![0u8; 32];
+	word[12..].copy_from_slice(&address);
+	word
+}
+
+fn bytes_tail(bytes: &[u8]) -> Vec<u8> {
+	let mut tail = word(bytes.len().into());
+	tail.extend_from_slice(bytes);
+	tail.resize(32 + (bytes.len() + 31) / 32 * 32, 0);
+	tail
+}
+
+fn call_input(method: [u8; 4], args: &[Vec<u8>]) -> Vec<u8> {
+	let mut input = method.to_vec();
+	args.iter().for_each(|arg| input.extend_from_slice(arg));
+	input
+}
+
+fn claim_input(case: &UserClaimTestCase, amount: U256, defi_user: U256) -> Vec<u8> {
+	let message = bytes_tail(&case.message);
+	let signature = bytes_tail(&case.icon_signature);
+	let mut proofs = word(case.merkle_proofs.len().into());
+	case.merkle_proofs
+		.iter()
+		.for_each(|proof| proofs.extend_from_slice(proof));
+
+	let head_size = 6 * 32;
+	let message_offset = head_size;
+	let signature_offset = message_offset + message.len();
+	let proofs_offset = signature_offset + signature.len();
+
+	call_input(
+		selector::CLAIM,
+		&[
+			address_word(case.icon_address),
+			word(message_offset.into()),
+			word(signature_offset.into()),
+			word(amount),
+			word(defi_user),
+			word(proofs_offset.into()),
+			message,
+			signature,
+			proofs,
+		],
+	)
+}
+
+fn execute(input: Vec<u8>, caller: H160) -> PrecompileResult {
+	let context = Context {
+		address: H160::repeat_byte(0xaa),
+		caller,
+		apparent_value: U256::zero(),
+	};
+	let mut handle = MockHandle::new(input, Some(u64::MAX), context);
+	AirdropPrecompile::<Test>::execute(&mut handle)
+}
+
+fn returned(output: Vec<u8>) -> PrecompileResult {
+	Ok(PrecompileOutput {
+		exit_status: ExitSucceed::Returned,
+		output,
+	})
+}
+
+fn reverted(message: &str) -> PrecompileResult {
+	Err(PrecompileFailure::Revert {
+		exit_status: ExitRevert::Reverted,
+		output: message.as_bytes().to_vec(),
+	})
+}
+
+#[test]
+fn read_methods_before_claim() {
+	minimal_test_ext().execute_with(|| {
+		let case = UserClaimTestCase::default();
+
+		assert_eq!(
+			execute(
+				call_input(selector::IS_CLAIMED, &[address_word(case.icon_address)]),
+				evm_claimer()
+			),
+			returned(word(U256::zero()))
+		);
+		assert_eq!(
+			execute(
+				call_input(selector::CLAIMED_AMOUNT, &[address_word(case.icon_address)]),
+				evm_claimer()
+			),
+			returned(word(U256::zero()))
+		);
+		assert_eq!(
+			execute(
+				call_input(
+					selector::LINKED_ICON_ADDRESS,
+					&[address_word(evm_claimer().to_fixed_bytes())]
+				),
+				evm_claimer()
+			),
+			returned(address_word([0u8; 20]))
+		);
+	});
+}
+
+#[test]
+fn claim_through_precompile() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+		let case = UserClaimTestCase::default();
+
+		assert_eq!(
+			execute(
+				claim_input(&case, case.amount.into(), U256::one()),
+				evm_claimer()
+			),
+			returned(vec![])
+		);
+
+		assert_eq!(
+			execute(
+				call_input(selector::IS_CLAIMED, &[address_word(case.icon_address)]),
+				evm_claimer()
+			),
+			returned(word(U256::one()))
+		);
+		assert_eq!(
+			execute(
+				call_input(selector::CLAIMED_AMOUNT, &[address_word(case.icon_address)]),
+				evm_claimer()
+			),
+			returned(word(case.amount.into()))
+		);
+		assert_eq!(
+			execute(
+				call_input(
+					selector::LINKED_ICON_ADDRESS,
+					&[address_word(evm_claimer().to_fixed_bytes())]
+				),
+				evm_claimer()
+			),
+			returned(address_word(case.icon_address))
+		);
+	});
+}
+
+#[test]
+fn claim_failure_is_reverted() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+		let case = UserClaimTestCase::default();
+
+		// Caller is not mapped to the ice address signed in the message
+		assert_eq!(
+			execute(
+				claim_input(&case, case.amount.into(), U256::one()),
+				H160::repeat_byte(0x01)
+			),
+			reverted("InvalidMessagePayload")
+		);
+		assert_eq!(AirdropModule::is_claimed(&case.icon_address), false);
+	});
+}
+
+#[test]
+fn claim_rejects_static_and_payable_calls() {
+	minimal_test_ext().execute_with(|| {
+		let case = UserClaimTestCase::default();
+		let input = claim_input(&case, case.amount.into(), U256::one());
+		let context = Context {
+			address: H160::repeat_byte(0xaa),
+			caller: evm_claimer(),
+			apparent_value: U256::zero(),
+		};
+
+		let mut handle = MockHandle::new(input.clone(), Some(u64::MAX), context.clone());
+		handle.is_static = true;
+		assert_eq!(
+			AirdropPrecompile::<Test>::execute(&mut handle),
+			reverted("airdrop: claim can not be called in static context")
+		);
+
+		let mut handle = MockHandle::new(
+			input,
+			Some(u64::MAX),
+			Context {
+				apparent_value: U256::one(),
+				..context
+			},
+		);
+		assert_eq!(
+			AirdropPrecompile::<Test>::execute(&mut handle),
+			reverted("airdrop: claim is not payable")
+		);
+	});
+}
+
+#[test]
+fn malformed_input() {
+	minimal_test_ext().execute_with(|| {
+		let case = UserClaimTestCase::default();
+
+		assert_eq!(
+			execute(vec![0x8c, 0xc0], evm_claimer()),
+			reverted("airdrop: invalid input")
+		);
+		assert_eq!(
+			execute(call_input([0u8; 4], &[]), evm_claimer()),
+			reverted("airdrop: unknown selector")
+		);
+		assert_eq!(
+			execute(
+				call_input(selector::IS_CLAIMED, &[vec![0u8; 31]]),
+				evm_claimer()
+			),
+			reverted("airdrop: input too short")
+		);
+
+		assert_eq!(
+			execute(
+				claim_input(&case, case.amount.into(), U256::from(2)),
+				evm_claimer()
+			),
+			reverted("airdrop: invalid bool")
+		);
+		assert_eq!(
+			execute(
+				claim_input(&case, U256::from(u128::MAX) + 1, U256::one()),
+				evm_claimer()
+			),
+			reverted("airdrop: amount overflow")
+		);
+
+		// Message offset pointing past the end of the input
+		let mut input = claim_input(&case, case.amount.into(), U256::one());
+		input[4 + 32..4 + 64].copy_from_slice(&word(U256::from(u32::MAX)));
+		assert_eq!(
+			execute(input, evm_claimer()),
+			reverted("airdrop: input too short")
+		);
+
+		// Message offset that does not fit in u32
+		let mut input = claim_input(&case, case.amount.into(), U256::one());
+		input[4 + 32..4 + 64].copy_from_slice(&word(U256::MAX));
+		assert_eq!(
+			execute(input, evm_claimer()),
+			reverted("airdrop: offset out of bounds")
+		);
+
+		// Truncated proofs array
+		let mut input = claim_input(&case, case.amount.into(), U256::one());
+		input.truncate(input.len() - 1);
+		assert_eq!(
+			execute(input, evm_claimer()),
+			reverted("airdrop: input too short")
+		);
+	});
+}
+
+#[test]
+fn claim_over_rate_limit_is_reverted() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+		let case = UserClaimTestCase::default();
+		assert_ok!(AirdropModule::set_claim_rate_limit(
+			RuntimeOrigin::root(),
+			Some(types::ClaimRateLimit {
+				max_amount: case.amount - 1,
+				period: 10,
+			})
+		));
+
+		// Caller sees the claim failing while claims stay blocked
+		assert_eq!(
+			execute(
+				claim_input(&case, case.amount.into(), U256::one()),
+				evm_claimer()
+			),
+			reverted("airdrop: claim rate limit exceeded")
+		);
+		assert_eq!(AirdropModule::is_claimed(&case.icon_address), false);
+		assert_eq!(true, AirdropModule::get_airdrop_state().block_claim_request);
+	});
+}
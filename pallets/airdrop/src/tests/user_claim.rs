@@ -1,19 +1,23 @@
 use super::prelude::*;
-use crate::{tests::UserClaimTestCase, Config, ServerAccount};
+use crate::{tests::UserClaimTestCase, Config};
 use frame_support::traits::Currency;
 
 #[test]
 fn claim_success() {
 	minimal_test_ext().execute_with(|| {
 		let server_account = samples::ACCOUNT_ID[0].into_account();
-		<ServerAccount<Test>>::put(server_account);
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			server_account,
+			Default::default(),
+		));
 		set_creditor_balance(10_000_0000);
 
 		let mut case = UserClaimTestCase::default();
 		case.amount = 12_017_332_u64.into();
 
 		assert_ok!(AirdropModule::dispatch_user_claim(
-			RuntimeOrigin::signed(AirdropModule::get_airdrop_server_account().unwrap()),
+			RuntimeOrigin::signed(server_account),
 			case.icon_address,
 			case.ice_address,
 			case.message,
@@ -32,19 +36,29 @@ fn claim_success() {
 
 		let expected_usable_amount;
 		let expected_vesting_block_number;
+		let expected_vesting_conversion;
 		let expected_icon_address = Some(case.icon_address);
 		if cfg!(feature = "no-vesting") {
 			expected_usable_amount = case.amount;
 			expected_vesting_block_number = None;
+			expected_vesting_conversion = None;
 		} else {
-			expected_usable_amount = 6761333;
+			expected_usable_amount = 6761332;
 			expected_vesting_block_number = Some(0);
+			expected_vesting_conversion = Some(types::VestingConversion {
+				converted_at: mock::MOCK_UNIX_TIME,
+				converted_at_block: 0,
+				duration: 5_256_000 * mock::MOCK_BLOCK_TIME,
+				block_time: mock::MOCK_BLOCK_TIME,
+				blocks: 5_256_000,
+			});
 		}
 
 		assert_eq!(total_balance, case.amount);
 		assert_eq!(usable_balance, expected_usable_amount);
 		assert_eq!(mapped_icon_wallet, expected_icon_address);
 		assert_eq!(snapshot.vesting_block_number, expected_vesting_block_number);
+		assert_eq!(snapshot.vesting_conversion, expected_vesting_conversion);
 		assert_eq!(snapshot.initial_transfer, usable_balance);
 		assert_eq!(snapshot.instant_block_number, Some(0));
 		assert_eq!(snapshot.done_instant, true);
@@ -52,6 +66,65 @@ fn claim_success() {
 	});
 }
 
+#[test]
+fn claim_vesting_from_claim_block() {
+	minimal_test_ext().execute_with(|| {
+		set_creditor_balance(10_000_0000);
+		// Later than vesting period in blocks so vesting can't end at absolute block
+		let claim_block = 10_000_000;
+		mock::System::set_block_number(claim_block);
+
+		let mut case = UserClaimTestCase::default();
+		case.amount = 12_017_332_u64.into();
+
+		assert_ok!(AirdropModule::dispatch_user_claim(
+			RuntimeOrigin::root(),
+			case.icon_address,
+			case.ice_address,
+			case.message,
+			case.icon_signature,
+			case.ice_signature,
+			case.amount,
+			case.defi_user,
+			case.merkle_proofs,
+		));
+
+		let ice_account = AirdropModule::convert_to_account_id(case.ice_address.clone()).unwrap();
+		let snapshot = <pallet_airdrop::IconSnapshotMap<Test>>::get(&case.icon_address).unwrap();
+		let usable_balance = <Test as Config>::Currency::usable_balance(&ice_account);
+		let schedules = pallet_vesting::Pallet::<Test>::vesting(&ice_account)
+			.map(|schedules| schedules.into_inner())
+			.unwrap_or_default();
+
+		let expected_usable_amount;
+		let expected_schedules;
+		if cfg!(feature = "no-vesting") {
+			expected_usable_amount = case.amount;
+			expected_schedules = vec![];
+		} else {
+			// Whole vesting is still locked and release 1 per block
+			// till `claim_block + 5_256_000`
+			expected_usable_amount = 6_761_332;
+			expected_schedules = vec![types::VestingInfoOf::<Test>::new(5_256_000, 1, claim_block)];
+			assert_eq!(
+				Some(5_256_000),
+				pallet_vesting::Pallet::<Test>::vesting_balance(&ice_account)
+			);
+			assert_eq!(
+				Some(claim_block as u32),
+				snapshot.vesting_conversion.map(|c| c.converted_at_block)
+			);
+		}
+
+		assert_eq!(usable_balance, expected_usable_amount);
+		assert_eq!(schedules, expected_schedules);
+		assert_eq!(
+			<Test as Config>::Currency::total_balance(&ice_account),
+			case.amount
+		);
+	});
+}
+
 #[test]
 fn insufficient_balance() {
 	let ofw_account = samples::ACCOUNT_ID[0].into_account();
@@ -59,7 +132,8 @@ fn insufficient_balance() {
 	test_ext.execute_with(|| {
 		assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			ofw_account
+			ofw_account,
+			Default::default(),
 		));
 
 		let mut case = UserClaimTestCase::default();
@@ -75,7 +149,7 @@ fn insufficient_balance() {
 
 		assert_err!(
 			AirdropModule::dispatch_user_claim(
-				RuntimeOrigin::signed(AirdropModule::get_airdrop_server_account().unwrap()),
+				RuntimeOrigin::signed(ofw_account),
 				case.icon_address,
 				case.ice_address.clone(),
 				case.message,
@@ -97,7 +171,8 @@ fn already_claimed() {
 	test_ext.execute_with(|| {
 		assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			ofw_account
+			ofw_account,
+			Default::default(),
 		));
 		let mut case = UserClaimTestCase::default();
 		let ice_account = AirdropModule::convert_to_account_id(case.ice_address).unwrap();
@@ -120,7 +195,7 @@ fn already_claimed() {
 
 		assert_err!(
 			AirdropModule::dispatch_user_claim(
-				RuntimeOrigin::signed(AirdropModule::get_airdrop_server_account().unwrap()),
+				RuntimeOrigin::signed(ofw_account),
 				case.icon_address,
 				case.ice_address.clone(),
 				case.message,
@@ -142,7 +217,8 @@ fn invalid_payload() {
 	test_ext.execute_with(|| {
         assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			ofw_account
+			ofw_account,
+			Default::default(),
 		));
 		let mut case = UserClaimTestCase::default();
 
@@ -159,7 +235,7 @@ fn invalid_payload() {
 
 		assert_err!(
 			AirdropModule::dispatch_user_claim(
-				RuntimeOrigin::signed(AirdropModule::get_airdrop_server_account().unwrap()),
+				RuntimeOrigin::signed(ofw_account),
 				case.icon_address,
 				case.ice_address.clone(),
 				case.message,
@@ -181,7 +257,8 @@ fn invalid_ice_signature() {
 	test_ext.execute_with(|| {
 		assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			ofw_account
+			ofw_account,
+			Default::default(),
 		));
 		let mut case = UserClaimTestCase::default();
 		case.ice_signature = [0u8; 64];
@@ -197,7 +274,7 @@ fn invalid_ice_signature() {
 
 		assert_err!(
 			AirdropModule::dispatch_user_claim(
-				RuntimeOrigin::signed(AirdropModule::get_airdrop_server_account().unwrap()),
+				RuntimeOrigin::signed(ofw_account),
 				case.icon_address,
 				case.ice_address.clone(),
 				case.message,
@@ -219,7 +296,8 @@ fn invalid_icon_signature() {
 	test_ext.execute_with(|| {
 		assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			ofw_account
+			ofw_account,
+			Default::default(),
 		));
 		let mut case = UserClaimTestCase::default();
 		case.icon_signature = [0u8; 65];
@@ -235,7 +313,7 @@ fn invalid_icon_signature() {
 
 		assert_err!(
 			AirdropModule::dispatch_user_claim(
-				RuntimeOrigin::signed(AirdropModule::get_airdrop_server_account().unwrap()),
+				RuntimeOrigin::signed(ofw_account),
 				case.icon_address,
 				case.ice_address.clone(),
 				case.message,
@@ -284,9 +362,9 @@ fn respect_vesting_pallet_min_transfer() {
 
 #[test]
 fn partial_transfer_can_reclaim() {
-	let vesting_period = Test::VESTING_TERMS.vesting_period;
 	minimal_test_ext().execute_with(|| {
 		run_to_block(1);
+		let (vesting_period, conversion) = utils::vesting_period_in_blocks::<Test>();
 
 		let mut case = UserClaimTestCase::default();
 		case.amount = 10_u64.pow(18).into();
@@ -301,10 +379,11 @@ fn partial_transfer_can_reclaim() {
 			utils::get_instant_percentage::<Test>(case.defi_user),
 		)
 		.unwrap();
-		let (vesting_schedule, reminding_amount) = utils::new_vesting_with_deadline::<
-			Test,
-			{ transfer::VESTING_APPLICABLE_FROM },
-		>(init_vesting_amount, vesting_period.into());
+		let (vesting_schedule, reminding_amount) = utils::new_vesting_between::<Test>(
+			init_vesting_amount,
+			conversion.unwrap().converted_at_block.into(),
+			vesting_period,
+		);
 		let vesting_amount = vesting_schedule.map(|s| s.locked()).unwrap_or(0u32.into());
 		let instant_amount = init_instant_amount + reminding_amount;
 
@@ -6,7 +6,8 @@ fn update_server_account() {
 		assert_noop!(
 			AirdropModule::set_airdrop_server_account(
 				RuntimeOrigin::none(),
-				samples::ACCOUNT_ID[1]
+				samples::ACCOUNT_ID[1],
+				Default::default(),
 			),
 			PalletError::DeniedOperation
 		);
@@ -14,19 +15,113 @@ fn update_server_account() {
 		assert_noop!(
 			AirdropModule::set_airdrop_server_account(
 				RuntimeOrigin::signed(samples::ACCOUNT_ID[1]),
-				samples::ACCOUNT_ID[2]
+				samples::ACCOUNT_ID[2],
+				Default::default(),
 			),
 			PalletError::DeniedOperation
 		);
 
 		assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			samples::ACCOUNT_ID[1]
+			samples::ACCOUNT_ID[1],
+			Default::default(),
+		));
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			samples::ACCOUNT_ID[2],
+			Default::default(),
+		));
+		assert!(AirdropModule::get_airdrop_server_account(&samples::ACCOUNT_ID[1]).is_some());
+		assert!(AirdropModule::get_airdrop_server_account(&samples::ACCOUNT_ID[2]).is_some());
+
+		// Updating quota of existing server account
+		let quota = types::ServerQuota {
+			claims: Some((5, types::QuotaPeriod::PerDay)),
+			total_amount: Some(1_000),
+		};
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			samples::ACCOUNT_ID[1],
+			quota.clone(),
 		));
 		assert_eq!(
-			Some(samples::ACCOUNT_ID[1]),
-			AirdropModule::get_airdrop_server_account()
+			quota,
+			AirdropModule::get_airdrop_server_account(&samples::ACCOUNT_ID[1])
+				.unwrap()
+				.quota
+		);
+	});
+}
+
+#[test]
+fn remove_server_account() {
+	minimal_test_ext().execute_with(|| {
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			samples::ACCOUNT_ID[1],
+			Default::default(),
+		));
+
+		assert_noop!(
+			AirdropModule::remove_airdrop_server_account(
+				RuntimeOrigin::signed(samples::ACCOUNT_ID[1]),
+				samples::ACCOUNT_ID[1],
+			),
+			PalletError::DeniedOperation
+		);
+
+		assert_noop!(
+			AirdropModule::remove_airdrop_server_account(
+				RuntimeOrigin::root(),
+				samples::ACCOUNT_ID[2],
+			),
+			PalletError::UnknownServerAccount
+		);
+
+		assert_ok!(AirdropModule::remove_airdrop_server_account(
+			RuntimeOrigin::root(),
+			samples::ACCOUNT_ID[1],
+		));
+		assert_eq!(
+			None,
+			AirdropModule::get_airdrop_server_account(&samples::ACCOUNT_ID[1])
+		);
+		assert_err!(
+			AirdropModule::ensure_root_or_server(RuntimeOrigin::signed(samples::ACCOUNT_ID[1]), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn max_server_accounts() {
+	minimal_test_ext().execute_with(|| {
+		use frame_support::traits::Get;
+		let max_accounts: u32 = <mock::Test as pallet_airdrop::Config>::MaxServerAccounts::get();
+
+		for i in 0..max_accounts {
+			assert_ok!(AirdropModule::set_airdrop_server_account(
+				RuntimeOrigin::root(),
+				sp_core::sr25519::Public([i as u8 + 100; 32]),
+				Default::default(),
+			));
+		}
+
+		assert_noop!(
+			AirdropModule::set_airdrop_server_account(
+				RuntimeOrigin::root(),
+				samples::ACCOUNT_ID[0],
+				Default::default(),
+			),
+			PalletError::TooManyServerAccounts
 		);
+
+		// Updating already existing one is still allowed
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			sp_core::sr25519::Public([100; 32]),
+			Default::default(),
+		));
 	});
 }
 
@@ -39,28 +134,153 @@ fn ensure_root_or_server() {
 		let server_account = samples::ACCOUNT_ID[0];
 		assert_ok!(AirdropModule::set_airdrop_server_account(
 			RuntimeOrigin::root(),
-			server_account.clone()
+			server_account.clone(),
+			Default::default(),
 		));
 
 		// root origin should pass
-		assert_ok!(AirdropModule::ensure_root_or_server(RuntimeOrigin::root()));
+		assert_ok!(AirdropModule::ensure_root_or_server(
+			RuntimeOrigin::root(),
+			100
+		));
 
 		// Any signed other than server account should fail
 		assert_err!(
-			AirdropModule::ensure_root_or_server(RuntimeOrigin::signed(samples::ACCOUNT_ID[2])),
+			AirdropModule::ensure_root_or_server(
+				RuntimeOrigin::signed(samples::ACCOUNT_ID[2]),
+				100
+			),
 			BadOrigin
 		);
 
 		// Unsigned origin should fail
 		assert_err!(
-			AirdropModule::ensure_root_or_server(RuntimeOrigin::none()),
+			AirdropModule::ensure_root_or_server(RuntimeOrigin::none(), 100),
 			BadOrigin
 		);
 
 		// Signed with server account should pass
-		assert_ok!(AirdropModule::ensure_root_or_server(RuntimeOrigin::signed(
-			server_account
-		)));
+		assert_ok!(AirdropModule::ensure_root_or_server(
+			RuntimeOrigin::signed(server_account),
+			100
+		));
+		assert_eq!(
+			100,
+			AirdropModule::get_airdrop_server_account(&server_account)
+				.unwrap()
+				.total_claimed
+		);
+	});
+}
+
+#[test]
+fn server_claim_quota_per_block() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		let server_account = samples::ACCOUNT_ID[0];
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			server_account,
+			types::ServerQuota {
+				claims: Some((2, types::QuotaPeriod::PerBlock)),
+				total_amount: None,
+			},
+		));
+
+		let origin = RuntimeOrigin::signed(server_account);
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 10));
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 10));
+		assert_eq!(
+			get_last_event(),
+			Some(
+				PalletEvent::ServerClaimQuotaReached {
+					account: server_account,
+					period: types::QuotaPeriod::PerBlock,
+				}
+				.into()
+			)
+		);
+		assert_err!(
+			AirdropModule::ensure_root_or_server(origin.clone(), 10),
+			PalletError::ServerQuotaExceeded
+		);
+
+		// Quota is refreshed in next block
+		run_to_block(3);
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 10));
+	});
+}
+
+#[test]
+fn server_claim_quota_per_day() {
+	minimal_test_ext().execute_with(|| {
+		use frame_support::traits::Get;
+		let blocks_per_day = <mock::Test as pallet_airdrop::Config>::BlocksPerDay::get();
+
+		let server_account = samples::ACCOUNT_ID[0];
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			server_account,
+			types::ServerQuota {
+				claims: Some((1, types::QuotaPeriod::PerDay)),
+				total_amount: None,
+			},
+		));
+
+		let origin = RuntimeOrigin::signed(server_account);
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 10));
+
+		run_to_block(blocks_per_day - 1);
+		assert_err!(
+			AirdropModule::ensure_root_or_server(origin.clone(), 10),
+			PalletError::ServerQuotaExceeded
+		);
+
+		run_to_block(blocks_per_day);
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 10));
+	});
+}
+
+#[test]
+fn server_amount_quota() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		let server_account = samples::ACCOUNT_ID[0];
+		assert_ok!(AirdropModule::set_airdrop_server_account(
+			RuntimeOrigin::root(),
+			server_account,
+			types::ServerQuota {
+				claims: None,
+				total_amount: Some(100),
+			},
+		));
+
+		let origin = RuntimeOrigin::signed(server_account);
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 60));
+		assert_err!(
+			AirdropModule::ensure_root_or_server(origin.clone(), 41),
+			PalletError::ServerQuotaExceeded
+		);
+		assert_ok!(AirdropModule::ensure_root_or_server(origin.clone(), 40));
+		assert_eq!(
+			get_last_event(),
+			Some(
+				PalletEvent::ServerAmountQuotaReached {
+					account: server_account,
+				}
+				.into()
+			)
+		);
+		assert_err!(
+			AirdropModule::ensure_root_or_server(origin.clone(), 1),
+			PalletError::ServerQuotaExceeded
+		);
+
+		// Root is never limited
+		assert_ok!(AirdropModule::ensure_root_or_server(
+			RuntimeOrigin::root(),
+			1_000
+		));
 	});
 }
 
@@ -632,6 +852,196 @@ fn insert_or_get_snapshot() {
 fn storage_version() {
 	minimal_test_ext().execute_with(|| {
 		let version = AirdropModule::get_storage_version();
-		assert_eq!(1_u32, version);
+		assert_eq!(3_u32, version);
+	});
+}
+
+#[test]
+fn change_merkle_root_with_scheme() {
+	minimal_test_ext().execute_with(|| {
+		run_to_block(2);
+		let new_root = [1u8; 32];
+
+		assert_noop!(
+			AirdropModule::change_merkle_root_with_scheme(
+				RuntimeOrigin::signed(samples::ACCOUNT_ID[1]),
+				new_root,
+				types::MerkleHashScheme::Keccak
+			),
+			PalletError::DeniedOperation
+		);
+
+		assert_eq!(
+			AirdropModule::get_merkle_hash_scheme(),
+			types::MerkleHashScheme::Blake2
+		);
+		assert_ok!(AirdropModule::change_merkle_root_with_scheme(
+			RuntimeOrigin::root(),
+			new_root,
+			types::MerkleHashScheme::Keccak
+		));
+		assert_eq!(AirdropModule::try_get_merkle_root(), Some(new_root));
+		assert_eq!(
+			AirdropModule::get_merkle_hash_scheme(),
+			types::MerkleHashScheme::Keccak
+		);
+		assert_eq!(
+			get_last_event(),
+			Some(
+				PalletEvent::MerkleHashSchemeUpdated {
+					old_scheme: types::MerkleHashScheme::Blake2,
+					new_scheme: types::MerkleHashScheme::Keccak,
+				}
+				.into()
+			)
+		);
+
+		// Plain root change fallback to default scheme
+		assert_ok!(AirdropModule::change_merkle_root(
+			RuntimeOrigin::root(),
+			new_root
+		));
+		assert_eq!(
+			AirdropModule::get_merkle_hash_scheme(),
+			types::MerkleHashScheme::Blake2
+		);
+	});
+}
+
+#[test]
+fn migrate_to_server_accounts() {
+	minimal_test_ext().execute_with(|| {
+		use frame_support::storage::migration::put_storage_value;
+
+		pallet_airdrop::StorageVersion::<Test>::put(1);
+		put_storage_value(
+			b"AirdropModule",
+			b"ServerAccount",
+			&[],
+			samples::ACCOUNT_ID[1],
+		);
+
+		pallet_airdrop::migrations::migrate_to_server_accounts::<Test>();
+
+		assert_eq!(2_u32, AirdropModule::get_storage_version());
+		assert_eq!(
+			Some(types::ServerAccountInfo::new(Default::default(), 0)),
+			AirdropModule::get_airdrop_server_account(&samples::ACCOUNT_ID[1])
+		);
+		assert_eq!(
+			None,
+			frame_support::storage::migration::get_storage_value::<types::AccountIdOf<Test>>(
+				b"AirdropModule",
+				b"ServerAccount",
+				&[]
+			)
+		);
+	});
+}
+
+#[test]
+fn migrate_to_vesting_conversion() {
+	minimal_test_ext().execute_with(|| {
+		use codec::Encode;
+		use frame_support::storage::{unhashed, StorageMap};
+		use pallet_airdrop::migrations::VESTING_CONVERSION_BATCH;
+
+		let icon_address = |index: u32| {
+			let mut icon_address = [0u8; 20];
+			icon_address[..4].copy_from_slice(&index.to_le_bytes());
+			icon_address
+		};
+		let old_snapshot = (
+			samples::ACCOUNT_ID[1],
+			1_000_u128,
+			true,
+			true,
+			false,
+			Option::<u64>::None,
+			Some(5_u64),
+			400_u128,
+		);
+		for index in 0..=VESTING_CONVERSION_BATCH {
+			unhashed::put_raw(
+				&pallet_airdrop::IconSnapshotMap::<Test>::hashed_key_for(icon_address(index)),
+				&old_snapshot.encode(),
+			);
+		}
+		pallet_airdrop::StorageVersion::<Test>::put(2);
+
+		// First block only translate a batch of snapshots
+		pallet_airdrop::migrations::migrate_to_vesting_conversion::<Test>();
+
+		assert_eq!(2_u32, AirdropModule::get_storage_version());
+		assert!(pallet_airdrop::VestingMigrationCursor::<Test>::get().is_some());
+		let migrated = (0..=VESTING_CONVERSION_BATCH)
+			.filter(|index| AirdropModule::get_icon_snapshot_map(icon_address(*index)).is_some())
+			.count();
+		assert_eq!(VESTING_CONVERSION_BATCH as usize, migrated);
+
+		// Claims are rejected until migration completes
+		assert_noop!(
+			AirdropModule::insert_or_get_snapshot(
+				&samples::ICON_ADDRESS[0],
+				&samples::ACCOUNT_ID[2].0,
+				false,
+				1_000
+			),
+			PalletError::MigrationInProgress
+		);
+
+		// Remaining snapshots are translated in next block
+		run_to_block(2);
+
+		assert_eq!(3_u32, AirdropModule::get_storage_version());
+		assert_eq!(None, pallet_airdrop::VestingMigrationCursor::<Test>::get());
+		for index in 0..=VESTING_CONVERSION_BATCH {
+			let snapshot = AirdropModule::get_icon_snapshot_map(icon_address(index)).unwrap();
+			assert_eq!(samples::ACCOUNT_ID[1], snapshot.ice_address);
+			assert_eq!(1_000, snapshot.amount);
+			assert_eq!(Some(5), snapshot.instant_block_number);
+			assert_eq!(400, snapshot.initial_transfer);
+			assert_eq!(None, snapshot.vesting_conversion);
+		}
+	});
+}
+
+#[test]
+fn vesting_period_in_blocks() {
+	minimal_test_ext().execute_with(|| {
+		let (ends_in, conversion) = utils::vesting_period_in_blocks::<Test>();
+
+		assert_eq!(5_256_000, ends_in);
+		assert_eq!(
+			Some(types::VestingConversion {
+				converted_at: mock::MOCK_UNIX_TIME,
+				converted_at_block: 0,
+				duration: 5_256_000 * mock::MOCK_BLOCK_TIME,
+				block_time: mock::MOCK_BLOCK_TIME,
+				blocks: 5_256_000,
+			}),
+			conversion
+		);
+
+		// Converted blocks are counted from current block
+		mock::System::set_block_number(1_000_000);
+		let (ends_in, conversion) = utils::vesting_period_in_blocks::<Test>();
+
+		assert_eq!(6_256_000, ends_in);
+		assert_eq!(
+			Some(1_000_000),
+			conversion.map(|conversion| conversion.converted_at_block)
+		);
+	});
+}
+
+#[test]
+fn vesting_period_as_blocks() {
+	minimal_test_ext().execute_with(|| {
+		let (blocks, conversion) =
+			utils::convert_vesting_period::<Test>(types::VestingPeriod::Blocks(7_776_000));
+
+		assert_eq!(7_776_000, blocks);
+		assert_eq!(None, conversion);
 	});
 }
@@ -9,7 +9,7 @@ use sp_runtime::traits::{CheckedAdd, Convert};
 pub const VESTING_APPLICABLE_FROM: u32 = 1u32;
 
 pub fn do_transfer<T: airdrop::Config>(snapshot: &mut types::SnapshotInfo<T>) -> DispatchResult {
-	let vesting_should_end_in = <T as airdrop::Config>::VESTING_TERMS.vesting_period;
+	let (vesting_should_end_in, vesting_conversion) = utils::vesting_period_in_blocks::<T>();
 	let creditor = AirdropModule::<T>::get_creditor_account()?;
 
 	let defi_user = snapshot.defi_user;
@@ -23,10 +23,12 @@ pub fn do_transfer<T: airdrop::Config>(snapshot: &mut types::SnapshotInfo<T>) ->
 				e
 			})?;
 
-	let (transfer_schedule, remaining_amount) = utils::new_vesting_with_deadline::<
-		T,
-		VESTING_APPLICABLE_FROM,
-	>(vesting_amount, vesting_should_end_in.into());
+	// Vesting given as duration starts from the block it was converted at
+	let vesting_starts_from = vesting_conversion
+		.map(|conversion| conversion.converted_at_block.into())
+		.unwrap_or_else(|| VESTING_APPLICABLE_FROM.into());
+	let (transfer_schedule, remaining_amount) =
+		utils::new_vesting_between::<T>(vesting_amount, vesting_starts_from, vesting_should_end_in);
 
 	// Amount to be transferred is:
 	// x% of total amount
@@ -62,6 +64,7 @@ pub fn do_transfer<T: airdrop::Config>(snapshot: &mut types::SnapshotInfo<T>) ->
 					let block_number = utils::get_current_block_number::<T>();
 					snapshot.done_vesting = true;
 					snapshot.vesting_block_number = Some(block_number);
+					snapshot.vesting_conversion = vesting_conversion;
 
 					info!("Vesting applied for {claimer:?} at height {block_number:?}");
 				}
@@ -58,6 +58,9 @@ pub type MerkleHash = [u8; 32];
 // pub type MerkleProofs=Vec<MerkleHash>;
 pub type MerkleProofs<T> = BoundedVec<MerkleHash, <T as Config>::MaxProofSize>;
 
+/// Raw storage key from where a multi-block migration continues
+pub type MigrationCursor = BoundedVec<u8, ConstU32<128>>;
+
 ///
 pub type VestingInfoOf<T> = pallet_vesting::VestingInfo<VestingBalanceOf<T>, BlockNumberOf<T>>;
 
@@ -98,6 +101,10 @@ pub struct SnapshotInfo<T: Config> {
 	pub instant_block_number: Option<BlockNumberOf<T>>,
 
 	pub initial_transfer: BalanceOf<T>,
+
+	// How vesting duration was converted to blocks
+	// when vesting period is defined as wall-clock duration
+	pub vesting_conversion: Option<VestingConversion>,
 }
 
 impl<T: Config> core::fmt::Debug for SnapshotInfo<T> {
@@ -111,6 +118,7 @@ impl<T: Config> core::fmt::Debug for SnapshotInfo<T> {
 			.field("vesting_block_number", &self.vesting_block_number)
 			.field("initial_transfer", &self.initial_transfer)
 			.field("instant_block_number", &self.instant_block_number)
+			.field("vesting_conversion", &self.vesting_conversion)
 			.finish()
 	}
 }
@@ -126,6 +134,7 @@ impl<T: Config> SnapshotInfo<T> {
 			vesting_block_number: None,
 			instant_block_number: None,
 			initial_transfer: 0u32.into(),
+			vesting_conversion: None,
 		}
 	}
 }
@@ -178,14 +187,134 @@ impl Default for AirdropState {
 	}
 }
 
+/// Period over which claim quota of server account is counted
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum QuotaPeriod {
+	PerBlock,
+	PerDay,
+}
+
+/// Limits applied to the claims dispatched by a server account
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug, Default)]
+pub struct ServerQuota<Balance> {
+	/// Maximum number of claims this server can dispatch in given period
+	pub claims: Option<(u32, QuotaPeriod)>,
+
+	/// Maximum total amount this server can dispatch over it's lifetime
+	pub total_amount: Option<Balance>,
+}
+
+/// Authorized server account along with it's quota and usage
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct ServerAccountInfo<BlockNumber, Balance> {
+	pub quota: ServerQuota<Balance>,
+
+	/// Block number from where current quota period started
+	pub period_start: BlockNumber,
+
+	/// Number of claims dispatched in current quota period
+	pub claims_in_period: u32,
+
+	/// Total amount this server have dispatched so far
+	pub total_claimed: Balance,
+}
+
+impl<BlockNumber, Balance: Default> ServerAccountInfo<BlockNumber, Balance> {
+	pub fn new(quota: ServerQuota<Balance>, period_start: BlockNumber) -> Self {
+		ServerAccountInfo {
+			quota,
+			period_start,
+			claims_in_period: 0,
+			total_claimed: Default::default(),
+		}
+	}
+}
+
+pub type ServerQuotaOf<T> = ServerQuota<BalanceOf<T>>;
+
+pub type ServerAccountInfoOf<T> = ServerAccountInfo<BlockNumberOf<T>, BalanceOf<T>>;
+
+/// Maximum amount that can be paid by user claims within given number of blocks
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct ClaimRateLimit<BlockNumber, Balance> {
+	pub max_amount: Balance,
+	pub period: BlockNumber,
+}
+
+/// Amount paid by user claims in the current rate limit window
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug, Default)]
+pub struct ClaimVolume<BlockNumber, Balance> {
+	pub window_start: BlockNumber,
+	pub amount: Balance,
+}
+
+pub type ClaimRateLimitOf<T> = ClaimRateLimit<BlockNumberOf<T>, BalanceOf<T>>;
+
+pub type ClaimVolumeOf<T> = ClaimVolume<BlockNumberOf<T>, BalanceOf<T>>;
+
+/// Hashing scheme with which the merkle tree of current root was constructed
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum MerkleHashScheme {
+	/// blake2_256 hashing with decimal-string amount in leaf
+	Blake2,
+
+	/// keccak256 hashing with `abi.encodePacked` leaf.
+	/// Tree built with this scheme can also be verified in solidity
+	Keccak,
+}
+
+impl Default for MerkleHashScheme {
+	fn default() -> Self {
+		MerkleHashScheme::Blake2
+	}
+}
+
 pub trait MerkelProofValidator<T: Config> {
-	fn validate(leaf_hash: MerkleHash, root_hash: MerkleHash, proofs: MerkleProofs<T>) -> bool;
+	fn validate(
+		leaf_hash: MerkleHash,
+		root_hash: MerkleHash,
+		proofs: MerkleProofs<T>,
+		scheme: MerkleHashScheme,
+	) -> bool;
 }
 
 pub struct VestingTerms {
 	pub defi_instant_percentage: u8,
 	pub non_defi_instant_percentage: u8,
-	pub vesting_period: u32,
+	pub vesting_period: VestingPeriod,
+}
+
+/// Time after which the airdrop vesting schedule should end
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum VestingPeriod {
+	/// Vesting ends after given number of blocks
+	Blocks(u32),
+
+	/// Vesting ends after given wall-clock duration in milliseconds.
+	/// This is converted to number of blocks at claim time using
+	/// `Config::ExpectedBlockTime`, so actual end time drifts if blocks
+	/// are produced slower or faster than expected
+	Duration(u64),
+}
+
+/// Record of wall-clock vesting duration being converted to blocks
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct VestingConversion {
+	/// Timestamp (in milliseconds) at which the conversion was made
+	pub converted_at: u64,
+
+	/// Block number at which the conversion was made
+	pub converted_at_block: u32,
+
+	/// Vesting duration in milliseconds
+	pub duration: u64,
+
+	/// Block time in milliseconds used for conversion
+	pub block_time: u64,
+
+	/// Number of blocks the duration was converted to.
+	/// Vesting ends at `converted_at_block + blocks`
+	pub blocks: u32,
 }
 
 pub const RAW_PAYLOAD_LENGTH: usize = b"icx_sendTransaction.data.{method.transfer.params.{wallet.b6e7a79d04e11a2dd43399f677878522523327cae2691b6cd1eb972b5a88eb48}}.dataType.call.from.hxb48f3bd3862d4a489fb3c9b761c4cfb20b34a645.nid.0x1.nonce.0x1.stepLimit.0x0.timestamp.0x0.to.hxb48f3bd3862d4a489fb3c9b761c4cfb20b34a645.version.0x3".len();
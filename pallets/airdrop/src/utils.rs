@@ -1,11 +1,14 @@
 use crate as airdrop;
 use airdrop::types;
 use codec::alloc::string::String;
-use frame_support::traits::Get;
+use frame_support::traits::{Get, UnixTime};
 use hex::FromHexError;
 use sp_core::H160;
 use sp_runtime::{
-	traits::{BlakeTwo256, Bounded, CheckedDiv, CheckedMul, CheckedSub, Convert, Saturating},
+	traits::{
+		BlakeTwo256, Bounded, CheckedDiv, CheckedMul, CheckedSub, Convert, Saturating,
+		UniqueSaturatedInto,
+	},
 	AccountId32, DispatchError,
 };
 use sp_std::vec::Vec;
@@ -18,6 +21,18 @@ pub fn new_vesting_with_deadline<T, const VESTING_APPLICABLE_FROM: u32>(
 	amount: types::VestingBalanceOf<T>,
 	ends_in: types::BlockNumberOf<T>,
 ) -> (Option<types::VestingInfoOf<T>>, types::VestingBalanceOf<T>)
+where
+	T: pallet_vesting::Config,
+{
+	new_vesting_between::<T>(amount, VESTING_APPLICABLE_FROM.into(), ends_in)
+}
+
+/// Same as `new_vesting_with_deadline` but with schedule starting from given block
+pub fn new_vesting_between<T>(
+	amount: types::VestingBalanceOf<T>,
+	starts_from: types::BlockNumberOf<T>,
+	ends_in: types::BlockNumberOf<T>,
+) -> (Option<types::VestingInfoOf<T>>, types::VestingBalanceOf<T>)
 where
 	T: pallet_vesting::Config,
 {
@@ -28,7 +43,8 @@ where
 	let vesting;
 
 	let ends_in_as_balance = BlockToBalance::<T>::convert(ends_in);
-	let transfer_over = ends_in_as_balance.saturating_sub(VESTING_APPLICABLE_FROM.into());
+	let transfer_over =
+		ends_in_as_balance.saturating_sub(BlockToBalance::<T>::convert(starts_from));
 
 	let idol_transfer_multiple = transfer_over * MIN_AMOUNT_PER_BLOCK.into();
 
@@ -45,7 +61,7 @@ where
 		vesting = Some(types::VestingInfoOf::<T>::new(
 			primary_transfer_amount,
 			per_block,
-			VESTING_APPLICABLE_FROM.into(),
+			starts_from,
 		));
 	} else {
 		vesting = None;
@@ -55,6 +71,43 @@ where
 	(vesting, remaining_amount)
 }
 
+/// Returns block number at which airdrop vesting should end
+/// as configured in `VESTING_TERMS`. See `convert_vesting_period`
+pub fn vesting_period_in_blocks<T: airdrop::Config>(
+) -> (types::BlockNumberOf<T>, Option<types::VestingConversion>) {
+	convert_vesting_period::<T>(T::VESTING_TERMS.vesting_period)
+}
+
+/// Converts given vesting period to block number at which vesting should end.
+/// Wall-clock duration is divided by `ExpectedBlockTime` and vests from current block,
+/// so vesting ends at `converted_at + duration` only as long as blocks are produced
+/// at the expected rate. The conversion is returned along so this estimate can be checked later
+pub fn convert_vesting_period<T: airdrop::Config>(
+	vesting_period: types::VestingPeriod,
+) -> (types::BlockNumberOf<T>, Option<types::VestingConversion>) {
+	match vesting_period {
+		types::VestingPeriod::Blocks(blocks) => (blocks.into(), None),
+		types::VestingPeriod::Duration(duration) => {
+			let block_time = T::ExpectedBlockTime::get().max(1);
+			let blocks = u32::try_from(duration / block_time).unwrap_or(u32::MAX);
+			let converted_at = u64::try_from(T::UnixTime::now().as_millis()).unwrap_or(u64::MAX);
+			let current_block = get_current_block_number::<T>();
+
+			let conversion = types::VestingConversion {
+				converted_at,
+				converted_at_block: current_block.unique_saturated_into(),
+				duration,
+				block_time,
+				blocks,
+			};
+			(
+				current_block.saturating_add(blocks.into()),
+				Some(conversion),
+			)
+		}
+	}
+}
+
 pub fn get_instant_percentage<T: airdrop::Config>(is_defi_user: bool) -> u8 {
 	if is_defi_user {
 		T::VESTING_TERMS.defi_instant_percentage
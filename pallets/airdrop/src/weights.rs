@@ -5,25 +5,46 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
 	fn set_airdrop_server_account() -> Weight;
+	fn remove_airdrop_server_account() -> Weight;
 	fn dispatch_user_claim() -> Weight;
 	fn dispatch_exchange_claim() -> Weight;
 	fn update_airdrop_state() -> Weight;
+	fn set_claim_rate_limit() -> Weight;
 	fn change_merkle_root() -> Weight;
+	fn change_merkle_root_with_scheme() -> Weight;
 }
 
 /// Weight functions for `pallet_airdrop`.
+///
+/// `remove_airdrop_server_account`, `set_claim_rate_limit` and `change_merkle_root_with_scheme`
+/// have not been benchmarked yet and reuse the execution time of the closest benchmarked call.
+/// Storage reads and writes follow the code. Regenerate this file with
+/// `benchmark pallet --pallet pallet_airdrop --extrinsic '*'` before relying on the numbers.
 pub struct AirDropWeightInfo<T>(PhantomData<T>);
 
 impl<T: frame_system::Config> WeightInfo for AirDropWeightInfo<T> {
-	// Storage: Airdrop ServerAccount (r:1 w:1)
+	// Storage: Airdrop ServerAccounts (r:1 w:1)
+	// Storage: Airdrop CounterForServerAccounts (r:1 w:1)
 	// Storage: System Number (r:1 w:0)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	fn set_airdrop_server_account() -> Weight {
 		Weight::from_ref_time(20_566_000)
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Airdrop ServerAccounts (r:1 w:1)
+	// Storage: Airdrop CounterForServerAccounts (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Not benchmarked, execution time of `set_airdrop_server_account`
+	fn remove_airdrop_server_account() -> Weight {
+		Weight::from_ref_time(20_566_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	// Storage: Airdrop AirdropChainState (r:1 w:0)
 	// Storage: Airdrop MerkleRoot (r:1 w:0)
@@ -37,10 +58,14 @@ impl<T: frame_system::Config> WeightInfo for AirDropWeightInfo<T> {
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Airdrop ServerAccounts (r:1 w:1)
+	// Storage: Airdrop ClaimRateLimit (r:1 w:0)
+	// Storage: Airdrop ClaimVolume (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
 	fn dispatch_user_claim() -> Weight {
 		Weight::from_ref_time(246_184_000)
-			.saturating_add(T::DbWeight::get().reads(13))
-			.saturating_add(T::DbWeight::get().writes(8))
+			.saturating_add(T::DbWeight::get().reads(17))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
 	// Storage: Airdrop AirdropChainState (r:1 w:0)
 	// Storage: Airdrop ExchangeAccountsMap (r:1 w:0)
@@ -55,11 +80,12 @@ impl<T: frame_system::Config> WeightInfo for AirDropWeightInfo<T> {
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	// Storage: Balances Locks (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
 	fn dispatch_exchange_claim() -> Weight {
 		Weight::from_ref_time(128_584_000)
 			// Standard Error: 156_000
 			.saturating_add(Weight::from_ref_time(392_000))
-			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().reads(15))
 			.saturating_add(T::DbWeight::get().writes(8))
 	}
 	// Storage: Airdrop AirdropChainState (r:1 w:1)
@@ -72,14 +98,38 @@ impl<T: frame_system::Config> WeightInfo for AirDropWeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	// Storage: Airdrop ClaimRateLimit (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Not benchmarked, execution time of `update_airdrop_state`
+	fn set_claim_rate_limit() -> Weight {
+		Weight::from_ref_time(20_384_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
 	// Storage: Airdrop MerkleRoot (r:1 w:1)
+	// Storage: Airdrop MerkleHashingScheme (r:1 w:1)
 	// Storage: System Number (r:1 w:0)
 	// Storage: System ExecutionPhase (r:1 w:0)
 	// Storage: System EventCount (r:1 w:1)
 	// Storage: System Events (r:1 w:1)
 	fn change_merkle_root() -> Weight {
 		Weight::from_ref_time(26_243_000)
-			.saturating_add(T::DbWeight::get().reads(5))
-			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: Airdrop MerkleRoot (r:1 w:1)
+	// Storage: Airdrop MerkleHashingScheme (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Not benchmarked, execution time of `change_merkle_root`
+	fn change_merkle_root_with_scheme() -> Weight {
+		Weight::from_ref_time(26_243_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(4))
 	}
 }
@@ -0,0 +1,138 @@
+//! RPC interface for the fees split pallet
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, DeserializeOwned, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_fees_split_runtime_api::{
+	FeeRecipient, FeeTotals, FeesSplitApi as FeesSplitRuntimeApi,
+};
+
+#[rpc(client, server)]
+pub trait FeesSplitApi<BlockHash, AccountId> {
+	/// Total fees, tips and evm fees ever received by recipient
+	#[method(name = "feesSplit_cumulativeFees")]
+	fn cumulative_fees(
+		&self,
+		recipient: FeeRecipient<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<FeeTotals<NumberOrHex>>;
+
+	/// Fees, tips and evm fees received by recipient in each retained
+	/// ledger period, oldest first
+	#[method(name = "feesSplit_periodFees")]
+	fn period_fees(
+		&self,
+		recipient: FeeRecipient<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u32, FeeTotals<NumberOrHex>)>>;
+}
+
+/// Provides RPC methods to query the fee ledger.
+/// `P` is the `(Block, Balance)` pair of the runtime
+pub struct FeesSplit<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> FeesSplit<C, P> {
+	/// Creates a new instance of the FeesSplit Rpc helper
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api
+pub enum Error {
+	/// The call to runtime failed
+	RuntimeError,
+	/// The amount does not fit into the response type
+	AmountConversionError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::AmountConversionError => 2,
+		}
+	}
+}
+
+fn into_number_or_hex<Balance: TryInto<NumberOrHex>>(
+	totals: FeeTotals<Balance>,
+) -> RpcResult<FeeTotals<NumberOrHex>> {
+	let convert = |amount: Balance| {
+		amount.try_into().map_err(|_| {
+			CallError::Custom(ErrorObject::owned(
+				Error::AmountConversionError.into(),
+				"Unable to represent amount as NumberOrHex",
+				None::<()>,
+			))
+		})
+	};
+
+	Ok(FeeTotals {
+		fees: convert(totals.fees)?,
+		tips: convert(totals.tips)?,
+		evm_fees: convert(totals.evm_fees)?,
+	})
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query the fee ledger",
+		Some(format!("{:?}", e)),
+	))
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance> FeesSplitApiServer<<Block as BlockT>::Hash, AccountId>
+	for FeesSplit<C, (Block, Balance)>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: FeesSplitRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn cumulative_fees(
+		&self,
+		recipient: FeeRecipient<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<FeeTotals<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let totals = api.cumulative_fees(&at, recipient).map_err(runtime_error)?;
+		into_number_or_hex(totals)
+	}
+
+	fn period_fees(
+		&self,
+		recipient: FeeRecipient<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u32, FeeTotals<NumberOrHex>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.period_fees(&at, recipient)
+			.map_err(runtime_error)?
+			.into_iter()
+			.map(|(period, totals)| Ok((period, into_number_or_hex(totals)?)))
+			.collect()
+	}
+}
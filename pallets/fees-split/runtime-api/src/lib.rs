@@ -0,0 +1,22 @@
+//! Runtime API definition for the fees split pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_fees_split::{FeeRecipient, FeeTotals};
+
+sp_api::decl_runtime_apis! {
+	pub trait FeesSplitApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Total fees, tips and evm fees ever received by recipient
+		fn cumulative_fees(recipient: FeeRecipient<AccountId>) -> FeeTotals<Balance>;
+
+		/// Fees, tips and evm fees received by recipient in each retained
+		/// ledger period, oldest first
+		fn period_fees(recipient: FeeRecipient<AccountId>) -> Vec<(u32, FeeTotals<Balance>)>;
+	}
+}
@@ -0,0 +1,155 @@
+use crate::types::{CalledContract, ContractAddress};
+use crate::{Config, Pallet};
+use frame_support::traits::IsSubType;
+use pallet_ethereum::{Transaction, TransactionAction};
+use pallet_evm::AddressMapping;
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{Hash, StaticLookup, UniqueSaturatedInto};
+use sp_std::marker::PhantomData;
+
+/// `CalledContract` of a runtime with `pallet_contracts`, `pallet_evm` and `pallet_ethereum`.
+/// Finds the contract of wasm contract calls, evm calls and ethereum transactions
+pub struct RuntimeContracts<T>(PhantomData<T>);
+
+impl<T> CalledContract<<T as frame_system::Config>::RuntimeCall, T::AccountId>
+	for RuntimeContracts<T>
+where
+	T: pallet_contracts::Config + pallet_evm::Config + pallet_ethereum::Config,
+	<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_contracts::Call<T>>
+		+ IsSubType<pallet_evm::Call<T>>
+		+ IsSubType<pallet_ethereum::Call<T>>,
+{
+	fn called_contract(
+		call: &<T as frame_system::Config>::RuntimeCall,
+	) -> Option<ContractAddress<T::AccountId>> {
+		if let Some(pallet_contracts::Call::call { dest, .. }) = call.is_sub_type() {
+			return T::Lookup::lookup(dest.clone())
+				.ok()
+				.map(ContractAddress::Wasm);
+		}
+
+		if let Some(pallet_evm::Call::call { target, .. }) = call.is_sub_type() {
+			return Some(ContractAddress::Evm(*target));
+		}
+
+		if let Some(pallet_ethereum::Call::transact { transaction }) = call.is_sub_type() {
+			let action = match transaction {
+				Transaction::Legacy(t) => &t.action,
+				Transaction::EIP2930(t) => &t.action,
+				Transaction::EIP1559(t) => &t.action,
+			};
+			return match action {
+				TransactionAction::Call(target) => Some(ContractAddress::Evm(*target)),
+				TransactionAction::Create => None,
+			};
+		}
+
+		None
+	}
+
+	fn deployed_contract(
+		call: &<T as frame_system::Config>::RuntimeCall,
+		deployer: &T::AccountId,
+	) -> Option<ContractAddress<T::AccountId>> {
+		match call.is_sub_type() {
+			Some(pallet_contracts::Call::instantiate_with_code { code, salt, .. }) => {
+				let code_hash = T::Hashing::hash(code);
+				return Some(ContractAddress::Wasm(
+					pallet_contracts::Pallet::<T>::contract_address(deployer, &code_hash, salt),
+				));
+			}
+			Some(pallet_contracts::Call::instantiate {
+				code_hash, salt, ..
+			}) => {
+				return Some(ContractAddress::Wasm(
+					pallet_contracts::Pallet::<T>::contract_address(deployer, code_hash, salt),
+				));
+			}
+			_ => {}
+		}
+
+		// Nonce of the signer was already checked and increased at this point,
+		// so current nonce of the source is the one the contract is created with
+		match call.is_sub_type() {
+			Some(pallet_evm::Call::create { source, .. }) => {
+				let account = T::AddressMapping::into_account_id(*source);
+				let nonce: u128 =
+					<frame_system::Pallet<T>>::account_nonce(&account).unique_saturated_into();
+				Some(ContractAddress::Evm(create_address(*source, nonce.into())))
+			}
+			Some(pallet_evm::Call::create2 {
+				source, init, salt, ..
+			}) => Some(ContractAddress::Evm(create2_address(*source, *salt, init))),
+			_ => None,
+		}
+	}
+
+	fn contract_exists(contract: &ContractAddress<T::AccountId>) -> bool {
+		match contract {
+			ContractAddress::Wasm(account) => {
+				pallet_contracts::Pallet::<T>::code_hash(account).is_some()
+			}
+			ContractAddress::Evm(address) => pallet_evm::AccountCodes::<T>::contains_key(address),
+		}
+	}
+}
+
+impl<T> RuntimeContracts<T>
+where
+	T: Config + pallet_contracts::Config + pallet_evm::Config + pallet_ethereum::Config,
+	<T as frame_system::Config>::RuntimeCall: IsSubType<pallet_ethereum::Call<T>>,
+{
+	/// Remember contract deployed by ethereum transaction signed by `source`.
+	/// Ethereum transactions are not charged through `pallet_transaction_payment`,
+	/// so `Pallet::record_deployment` must be called once the transaction is applied
+	pub fn note_ethereum_deployment(call: &<T as frame_system::Config>::RuntimeCall, source: H160) {
+		let (action, nonce) = match call.is_sub_type() {
+			Some(pallet_ethereum::Call::transact { transaction }) => match transaction {
+				Transaction::Legacy(t) => (&t.action, t.nonce),
+				Transaction::EIP2930(t) => (&t.action, t.nonce),
+				Transaction::EIP1559(t) => (&t.action, t.nonce),
+			},
+			_ => return,
+		};
+
+		if let TransactionAction::Create = action {
+			Pallet::<T>::note_deployed_contract(
+				ContractAddress::Evm(create_address(source, nonce)),
+				T::AddressMapping::into_account_id(source),
+			);
+		}
+	}
+}
+
+/// Address of contract created by `source` with `CREATE`,
+/// that is `keccak256(rlp([source, nonce]))[12..]`
+pub fn create_address(source: H160, nonce: U256) -> H160 {
+	let mut nonce_bytes = [0u8; 32];
+	nonce.to_big_endian(&mut nonce_bytes);
+	let nonce_bytes = &nonce_bytes[nonce_bytes.iter().take_while(|b| **b == 0).count()..];
+
+	// Nonce is a single byte only when it is below 0x80, otherwise it is length prefixed
+	let nonce_item = match nonce_bytes {
+		[] => sp_std::vec![0x80],
+		[byte] if *byte < 0x80 => sp_std::vec![*byte],
+		bytes => [&[0x80 + bytes.len() as u8][..], bytes].concat(),
+	};
+
+	let mut stream = sp_std::vec![0xc0 + (21 + nonce_item.len()) as u8, 0x94];
+	stream.extend_from_slice(source.as_bytes());
+	stream.extend_from_slice(&nonce_item);
+
+	H160::from_slice(&keccak_256(&stream)[12..])
+}
+
+/// Address of contract created by `source` with `CREATE2`,
+/// that is `keccak256(0xff ++ source ++ salt ++ keccak256(init))[12..]`
+pub fn create2_address(source: H160, salt: H256, init: &[u8]) -> H160 {
+	let mut stream = sp_std::vec![0xff];
+	stream.extend_from_slice(source.as_bytes());
+	stream.extend_from_slice(salt.as_bytes());
+	stream.extend_from_slice(&keccak_256(init));
+
+	H160::from_slice(&keccak_256(&stream)[12..])
+}
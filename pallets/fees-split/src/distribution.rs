@@ -0,0 +1,93 @@
+use crate::types::{ContractAddressOf, FeeKind, FeeRecipient, FeeRecipientOf, NegativeImbalanceOf};
+use crate::{Config, Pallet};
+use frame_support::traits::{Currency, Get, Imbalance, OnUnbalanced};
+use sp_runtime::{traits::Zero, Perbill};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// `OnUnbalanced` handler that distribute transaction fees
+/// and tips among the recipients configured in this pallet
+pub struct DistributeFees<T>(PhantomData<T>);
+
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for DistributeFees<T> {
+	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalanceOf<T>>) {
+		if let Some(fees) = fees_then_tips.next() {
+			Pallet::<T>::distribute(fees, Pallet::<T>::fee_shares(), FeeKind::Fee);
+
+			if let Some(tips) = fees_then_tips.next() {
+				Pallet::<T>::distribute(tips, Pallet::<T>::tip_shares(), FeeKind::Tip);
+			}
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Distribute fee of a transaction. When the called contract is registered
+	/// and approved, the rebate share is first given to its beneficiary
+	pub fn distribute_fee(
+		amount: NegativeImbalanceOf<T>,
+		kind: FeeKind,
+		contract: Option<ContractAddressOf<T>>,
+	) {
+		let beneficiary = contract.and_then(|contract| Self::rebate_beneficiary(&contract));
+		let amount = match beneficiary {
+			Some(beneficiary) => {
+				let rebate = Self::rebate_share() * amount.peek();
+				let (rebate, rest) = amount.split(rebate);
+				Self::pay(FeeRecipient::Account(beneficiary), rebate, kind);
+				rest
+			}
+			None => amount,
+		};
+
+		Self::distribute(amount, Self::fee_shares(), kind);
+	}
+
+	/// Split given amount according to the shares.
+	/// Last recipient also receive the remainder left by rounding.
+	/// Every paid part is recorded in the fee ledger under given kind
+	pub fn distribute(
+		amount: NegativeImbalanceOf<T>,
+		shares: Vec<(FeeRecipientOf<T>, Perbill)>,
+		kind: FeeKind,
+	) {
+		let total = amount.peek();
+		let last = shares.len().saturating_sub(1);
+		let mut remaining = amount;
+
+		for (index, (recipient, share)) in shares.into_iter().enumerate() {
+			let part = if index == last {
+				sp_std::mem::replace(&mut remaining, NegativeImbalanceOf::<T>::zero())
+			} else {
+				let (part, rest) = remaining.split(share * total);
+				remaining = rest;
+				part
+			};
+			Self::pay(recipient, part, kind);
+		}
+
+		// Something remains only when there was no share at all
+		if !remaining.peek().is_zero() {
+			Self::pay(FeeRecipient::Treasury, remaining, kind);
+		}
+	}
+
+	fn pay(recipient: FeeRecipientOf<T>, amount: NegativeImbalanceOf<T>, kind: FeeKind) {
+		// Collator pot share goes to treasury when chain have no pot
+		let recipient = match recipient {
+			FeeRecipient::CollatorPot if T::CollatorPot::get().is_none() => FeeRecipient::Treasury,
+			recipient => recipient,
+		};
+		Self::record(recipient.clone(), kind, amount.peek());
+
+		match recipient {
+			FeeRecipient::Treasury => T::Treasury::on_unbalanced(amount),
+			FeeRecipient::Author => T::Author::on_unbalanced(amount),
+			FeeRecipient::Burn => drop(amount),
+			FeeRecipient::CollatorPot => match T::CollatorPot::get() {
+				Some(pot) => T::Currency::resolve_creating(&pot, amount),
+				None => T::Treasury::on_unbalanced(amount),
+			},
+			FeeRecipient::Account(who) => T::Currency::resolve_creating(&who, amount),
+		}
+	}
+}
@@ -0,0 +1,92 @@
+use crate::types::{AccountIdOf, BalanceOf, FeeKind, NegativeImbalanceOf};
+use crate::{Config, CurrentContract, Pallet};
+use frame_support::traits::{
+	Currency, ExistenceRequirement, Imbalance, SignedImbalance, WithdrawReasons,
+};
+use pallet_evm::{AddressMapping, OnChargeEVMTransaction};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{Saturating, UniqueSaturatedInto, Zero};
+use sp_std::marker::PhantomData;
+
+type PositiveImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::PositiveImbalance;
+
+/// `OnChargeEVMTransaction` implementation that withdraw the fee from caller
+/// and refund the unused gas. The base fee part is distributed like substrate fees
+/// and the priority fee part like substrate tips.
+/// Base fee of a call to a registered contract is subject to rebates
+pub struct DistributeEvmFees<T>(PhantomData<T>);
+
+impl<T> OnChargeEVMTransaction<T> for DistributeEvmFees<T>
+where
+	T: Config + pallet_evm::Config,
+	U256: UniqueSaturatedInto<BalanceOf<T>>,
+{
+	type LiquidityInfo = Option<NegativeImbalanceOf<T>>;
+
+	fn withdraw_fee(who: &H160, fee: U256) -> Result<Self::LiquidityInfo, pallet_evm::Error<T>> {
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(*who);
+		let imbalance = T::Currency::withdraw(
+			&account_id,
+			fee.unique_saturated_into(),
+			WithdrawReasons::FEE,
+			ExistenceRequirement::AllowDeath,
+		)
+		.map_err(|_| pallet_evm::Error::<T>::BalanceLow)?;
+
+		Ok(Some(imbalance))
+	}
+
+	fn correct_and_deposit_fee(
+		who: &H160,
+		corrected_fee: U256,
+		base_fee: U256,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Self::LiquidityInfo {
+		// Forget the called contract even when no fee was paid,
+		// so it can not leak into the next transaction
+		let contract = <CurrentContract<T>>::take();
+		let paid = already_withdrawn?;
+		let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(*who);
+
+		// Refund the unused gas
+		let refund_amount = paid
+			.peek()
+			.saturating_sub(corrected_fee.unique_saturated_into());
+		let refund_imbalance = T::Currency::deposit_into_existing(&account_id, refund_amount)
+			.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
+
+		// Account may have been reaped while withdrawing the fee
+		let refund_imbalance = if T::Currency::minimum_balance().is_zero()
+			&& !refund_amount.is_zero()
+			&& T::Currency::total_balance(&account_id).is_zero()
+		{
+			match T::Currency::make_free_balance_be(&account_id, refund_amount) {
+				SignedImbalance::Positive(p) => p,
+				_ => PositiveImbalanceOf::<T>::zero(),
+			}
+		} else {
+			refund_imbalance
+		};
+
+		let adjusted_paid = paid
+			.offset(refund_imbalance)
+			.same()
+			.unwrap_or_else(|_| NegativeImbalanceOf::<T>::zero());
+
+		let (base_fee, tip) = adjusted_paid.split(base_fee.unique_saturated_into());
+		Pallet::<T>::distribute_fee(base_fee, FeeKind::Evm, contract);
+
+		Some(tip)
+	}
+
+	fn pay_priority_fee(tip: Self::LiquidityInfo) {
+		if let Some(tip) = tip {
+			Pallet::<T>::distribute(tip, Pallet::<T>::tip_shares(), FeeKind::Evm);
+		}
+	}
+}
@@ -5,26 +5,149 @@ pub use pallet::*;
 
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
+use sp_runtime::Perbill;
+use sp_std::prelude::*;
+
+/// All the types and aliases used in this pallet
+pub mod types;
+
+/// Reusable `OnUnbalanced` implementation distributing fees
+pub mod distribution;
+
+/// Charging and distribution of evm transaction fees
+pub mod evm;
+
+/// Charging and distribution of substrate transaction fees
+pub mod transaction;
+
+/// Contracts called by transactions of a runtime
+pub mod contracts;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub use contracts::RuntimeContracts;
+pub use distribution::DistributeFees;
+pub use evm::DistributeEvmFees;
+pub use transaction::ChargeTransactionFees;
+pub use types::{CalledContract, ContractAddress, FeeKind, FeeRecipient, FeeTotals};
 
 const MAX_PERCENT: u32 = 100;
 
+/// Maximum number of expired ledger entries removed in a single block
+pub const LEDGER_REMOVAL_LIMIT: u32 = 100;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
+	use frame_support::traits::{OnUnbalanced, ReservableCurrency};
+	use sp_runtime::traits::{UniqueSaturatedInto, Zero};
+	use types::{
+		BalanceOf, ContractAddressOf, ContractRebate, ContractRebateOf, FeeRecipientOf,
+		FeeRecipientsOf, FeeTotalsOf, LedgerCursor, NegativeImbalanceOf, PeriodIndex,
+	};
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// More recipients than `MaxRecipients` were given
+		TooManyRecipients,
+		/// Shares of recipients does not sum up to 100%
+		InvalidShares,
+		/// Treasury cut is more than 100 percent
+		InvalidTreasuryCut,
+		/// Contract is already registered for rebates
+		RebateAlreadyRegistered,
+		/// Contract is not registered for rebates
+		RebateNotRegistered,
+		/// Registration of contract is already approved
+		RebateAlreadyApproved,
+		/// Caller is neither the requester nor the update origin
+		NotRebateRequester,
+		/// Caller is not recorded as deployer of the contract
+		NotContractDeployer,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Treasury cut percent have been changed
+		TreasuryCutChanged { old: u32, new: u32 },
+		/// Recipients of fees have been changed
+		FeeRecipientsChanged { recipients: FeeRecipientsOf<T> },
+		/// Recipients of tips have been changed
+		TipRecipientsChanged { recipients: FeeRecipientsOf<T> },
+		/// Share of contract call fees given as rebate have been changed
+		RebateShareChanged { old: Perbill, new: Perbill },
+		/// Rebates for a contract have been requested and wait for approval
+		RebateRequested {
+			contract: ContractAddressOf<T>,
+			requester: T::AccountId,
+			beneficiary: T::AccountId,
+		},
+		/// Rebates for a contract have been approved
+		RebateApproved { contract: ContractAddressOf<T> },
+		/// Registration of a contract have been removed
+		RebateRemoved { contract: ContractAddressOf<T> },
+	}
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {}
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Currency in which fees are collected
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// Handler of the treasury share
+		type Treasury: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Handler of the block author share
+		type Author: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Account of collator reward pot, if the chain have one.
+		/// Otherwise the collator pot share is sent to treasury
+		type CollatorPot: Get<Option<Self::AccountId>>;
+
+		/// Origin allowed to change the fee split configuration
+		/// and to approve contract rebates
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Find the contract called by a transaction
+		type CalledContract: CalledContract<
+			<Self as frame_system::Config>::RuntimeCall,
+			Self::AccountId,
+		>;
+
+		/// Amount reserved when requesting rebates for a contract
+		#[pallet::constant]
+		type RebateDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of fee recipients
+		#[pallet::constant]
+		type MaxRecipients: Get<u32>;
+
+		/// Length in blocks of a fee ledger period
+		#[pallet::constant]
+		type LedgerPeriod: Get<Self::BlockNumber>;
+
+		/// Number of most recent periods kept in the fee ledger
+		#[pallet::constant]
+		type LedgerRetainedPeriods: Get<u32>;
+	}
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub treasury_cut_percent: u32,
+		#[cfg_attr(feature = "std", serde(default))]
+		pub fee_recipients: Vec<(FeeRecipientOf<T>, Perbill)>,
+		#[cfg_attr(feature = "std", serde(default))]
+		pub tip_recipients: Vec<(FeeRecipientOf<T>, Perbill)>,
 		_marker: PhantomData<T>,
 	}
 
@@ -33,6 +156,8 @@ pub mod pallet {
 		pub fn new(treasury_cut_percent: u32) -> Self {
 			Self {
 				treasury_cut_percent,
+				fee_recipients: Vec::new(),
+				tip_recipients: Vec::new(),
 				_marker: PhantomData,
 			}
 		}
@@ -43,6 +168,8 @@ pub mod pallet {
 		fn default() -> Self {
 			Self {
 				treasury_cut_percent: 80,
+				fee_recipients: Vec::new(),
+				tip_recipients: Vec::new(),
 				_marker: PhantomData,
 			}
 		}
@@ -51,7 +178,19 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
+			assert!(
+				self.treasury_cut_percent <= MAX_PERCENT,
+				"Treasury cut percent must not exceed 100"
+			);
 			<TreasuryCutPercent<T>>::put(self.treasury_cut_percent);
+
+			let recipients = Pallet::<T>::validate_recipients(self.fee_recipients.clone())
+				.expect("Invalid fee recipients in genesis");
+			<FeeRecipients<T>>::put(recipients);
+
+			let recipients = Pallet::<T>::validate_recipients(self.tip_recipients.clone())
+				.expect("Invalid tip recipients in genesis");
+			<TipRecipients<T>>::put(recipients);
 		}
 	}
 
@@ -59,6 +198,80 @@ pub mod pallet {
 	#[pallet::getter(fn treasury_cut_percent)]
 	pub type TreasuryCutPercent<T> = StorageValue<_, u32, ValueQuery>;
 
+	/// Recipients of fees with their share.
+	/// When empty, fees are split between treasury and author by `TreasuryCutPercent`
+	#[pallet::storage]
+	#[pallet::getter(fn fee_recipients)]
+	pub type FeeRecipients<T: Config> = StorageValue<_, FeeRecipientsOf<T>, ValueQuery>;
+
+	/// Recipients of tips with their share.
+	/// When empty, all tips are given to block author
+	#[pallet::storage]
+	#[pallet::getter(fn tip_recipients)]
+	pub type TipRecipients<T: Config> = StorageValue<_, FeeRecipientsOf<T>, ValueQuery>;
+
+	/// Share of fees of transactions calling a registered contract
+	/// that is given to the contract beneficiary
+	#[pallet::storage]
+	#[pallet::getter(fn rebate_share)]
+	pub type RebateShare<T> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// Contracts registered for developer fee rebates
+	#[pallet::storage]
+	#[pallet::getter(fn contract_rebates)]
+	pub type ContractRebates<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContractAddressOf<T>, ContractRebateOf<T>>;
+
+	/// Contract called by the ethereum transaction being applied
+	#[pallet::storage]
+	pub(super) type CurrentContract<T: Config> = StorageValue<_, ContractAddressOf<T>>;
+
+	/// Accounts that deployed contracts, recorded when the deploying transaction is applied
+	#[pallet::storage]
+	#[pallet::getter(fn contract_deployer)]
+	pub type ContractDeployers<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContractAddressOf<T>, T::AccountId>;
+
+	/// Contract that the transaction being applied would deploy, along with its deployer
+	#[pallet::storage]
+	pub(super) type PendingDeployment<T: Config> =
+		StorageValue<_, (ContractAddressOf<T>, T::AccountId)>;
+
+	/// Total amounts ever received by each recipient
+	#[pallet::storage]
+	#[pallet::getter(fn cumulative_fees)]
+	pub type CumulativeFees<T: Config> =
+		StorageMap<_, Blake2_128Concat, FeeRecipientOf<T>, FeeTotalsOf<T>, ValueQuery>;
+
+	/// Amounts received by each recipient within a ledger period.
+	/// Only the last `LedgerRetainedPeriods` periods are kept
+	#[pallet::storage]
+	#[pallet::getter(fn period_fees)]
+	pub type PeriodFees<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PeriodIndex,
+		Blake2_128Concat,
+		FeeRecipientOf<T>,
+		FeeTotalsOf<T>,
+		ValueQuery,
+	>;
+
+	/// Oldest ledger period that may still have entries
+	#[pallet::storage]
+	pub(super) type OldestLedgerPeriod<T> = StorageValue<_, PeriodIndex, ValueQuery>;
+
+	/// Raw key from where removal of `OldestLedgerPeriod` continues
+	#[pallet::storage]
+	pub(super) type LedgerRemovalCursor<T> = StorageValue<_, LedgerCursor>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			Self::remove_expired_periods(n, LEDGER_REMOVAL_LIMIT)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight((
@@ -66,20 +279,402 @@ pub mod pallet {
 		DispatchClass::Operational,
 		))]
 		pub fn set_treasury_cut_percent(origin: OriginFor<T>, val: u32) -> DispatchResult {
-			ensure_root(origin)?;
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(val <= MAX_PERCENT, Error::<T>::InvalidTreasuryCut);
+
+			let old = Self::treasury_cut_percent();
+			<TreasuryCutPercent<T>>::set(val);
+
+			Self::deposit_event(Event::TreasuryCutChanged { old, new: val });
+			Ok(())
+		}
+
+		/// Set recipients of fees along with their share.
+		/// Shares must sum up to 100% unless the list is empty
+		#[pallet::weight((
+		WeightInfo::<T>::set_fee_recipients(recipients.len() as u32),
+		DispatchClass::Operational,
+		))]
+		pub fn set_fee_recipients(
+			origin: OriginFor<T>,
+			recipients: Vec<(FeeRecipientOf<T>, Perbill)>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let recipients = Self::validate_recipients(recipients)?;
+			<FeeRecipients<T>>::put(recipients.clone());
+
+			Self::deposit_event(Event::FeeRecipientsChanged { recipients });
+			Ok(())
+		}
+
+		/// Set recipients of tips along with their share.
+		/// Shares must sum up to 100% unless the list is empty
+		#[pallet::weight((
+		WeightInfo::<T>::set_tip_recipients(recipients.len() as u32),
+		DispatchClass::Operational,
+		))]
+		pub fn set_tip_recipients(
+			origin: OriginFor<T>,
+			recipients: Vec<(FeeRecipientOf<T>, Perbill)>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let recipients = Self::validate_recipients(recipients)?;
+			<TipRecipients<T>>::put(recipients.clone());
+
+			Self::deposit_event(Event::TipRecipientsChanged { recipients });
+			Ok(())
+		}
+
+		/// Set share of fees given to beneficiary of called contract
+		#[pallet::weight((
+		WeightInfo::<T>::set_config_with_u32(),
+		DispatchClass::Operational,
+		))]
+		pub fn set_rebate_share(origin: OriginFor<T>, share: Perbill) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let old = Self::rebate_share();
+			<RebateShare<T>>::put(share);
+
+			Self::deposit_event(Event::RebateShareChanged { old, new: share });
+			Ok(())
+		}
+
+		/// Request fee rebates of a contract to be paid to beneficiary.
+		/// Only the recorded deployer of the contract can request rebates.
+		/// `RebateDeposit` is reserved from caller until the registration is removed
+		#[pallet::weight(WeightInfo::<T>::request_contract_rebate())]
+		pub fn request_contract_rebate(
+			origin: OriginFor<T>,
+			contract: ContractAddressOf<T>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			let requester = ensure_signed(origin)?;
+			ensure!(
+				!<ContractRebates<T>>::contains_key(&contract),
+				Error::<T>::RebateAlreadyRegistered
+			);
+			ensure!(
+				Self::contract_deployer(&contract).as_ref() == Some(&requester),
+				Error::<T>::NotContractDeployer
+			);
+
+			let deposit = T::RebateDeposit::get();
+			T::Currency::reserve(&requester, deposit)?;
+			<ContractRebates<T>>::insert(
+				&contract,
+				ContractRebate {
+					requester: requester.clone(),
+					beneficiary: beneficiary.clone(),
+					deposit,
+					approved: false,
+				},
+			);
 
-			if val <= MAX_PERCENT {
-				<TreasuryCutPercent<T>>::set(val);
+			Self::deposit_event(Event::RebateRequested {
+				contract,
+				requester,
+				beneficiary,
+			});
+			Ok(())
+		}
+
+		/// Approve requested rebates of a contract
+		#[pallet::weight((
+		WeightInfo::<T>::approve_contract_rebate(),
+		DispatchClass::Operational,
+		))]
+		pub fn approve_contract_rebate(
+			origin: OriginFor<T>,
+			contract: ContractAddressOf<T>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			<ContractRebates<T>>::try_mutate(&contract, |rebate| -> DispatchResult {
+				let rebate = rebate.as_mut().ok_or(Error::<T>::RebateNotRegistered)?;
+				ensure!(!rebate.approved, Error::<T>::RebateAlreadyApproved);
+				rebate.approved = true;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RebateApproved { contract });
+			Ok(())
+		}
+
+		/// Register approved rebates of a contract without deployer record,
+		/// such as contracts deployed before deployers were recorded.
+		/// No deposit is reserved and beneficiary is treated as the requester
+		#[pallet::weight((
+		WeightInfo::<T>::force_register_contract_rebate(),
+		DispatchClass::Operational,
+		))]
+		pub fn force_register_contract_rebate(
+			origin: OriginFor<T>,
+			contract: ContractAddressOf<T>,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				!<ContractRebates<T>>::contains_key(&contract),
+				Error::<T>::RebateAlreadyRegistered
+			);
+
+			<ContractRebates<T>>::insert(
+				&contract,
+				ContractRebate {
+					requester: beneficiary.clone(),
+					beneficiary: beneficiary.clone(),
+					deposit: Zero::zero(),
+					approved: true,
+				},
+			);
+
+			Self::deposit_event(Event::RebateRequested {
+				contract: contract.clone(),
+				requester: beneficiary.clone(),
+				beneficiary,
+			});
+			Self::deposit_event(Event::RebateApproved { contract });
+			Ok(())
+		}
+
+		/// Remove the registration of a contract and release the deposit.
+		/// Can be called by the requester or by update origin to reject or revoke
+		#[pallet::weight(WeightInfo::<T>::remove_contract_rebate())]
+		pub fn remove_contract_rebate(
+			origin: OriginFor<T>,
+			contract: ContractAddressOf<T>,
+		) -> DispatchResult {
+			let rebate =
+				Self::contract_rebates(&contract).ok_or(Error::<T>::RebateNotRegistered)?;
+			if T::UpdateOrigin::try_origin(origin.clone()).is_err() {
+				let who = ensure_signed(origin)?;
+				ensure!(who == rebate.requester, Error::<T>::NotRebateRequester);
 			}
 
+			T::Currency::unreserve(&rebate.requester, rebate.deposit);
+			<ContractRebates<T>>::remove(&contract);
+
+			Self::deposit_event(Event::RebateRemoved { contract });
 			Ok(())
 		}
 	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn validate_recipients(
+			recipients: Vec<(FeeRecipientOf<T>, Perbill)>,
+		) -> Result<FeeRecipientsOf<T>, Error<T>> {
+			let recipients: FeeRecipientsOf<T> = recipients
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyRecipients)?;
+
+			let total_parts = recipients
+				.iter()
+				.map(|(_, share)| share.deconstruct() as u64)
+				.sum::<u64>();
+			ensure!(
+				recipients.is_empty() || total_parts == Perbill::one().deconstruct() as u64,
+				Error::<T>::InvalidShares
+			);
+
+			Ok(recipients)
+		}
+
+		/// Shares in which fees are currently distributed
+		pub fn fee_shares() -> Vec<(FeeRecipientOf<T>, Perbill)> {
+			let recipients = Self::fee_recipients();
+			if !recipients.is_empty() {
+				return recipients.into_inner();
+			}
+
+			let treasury_cut = Perbill::from_percent(Self::treasury_cut_percent().min(MAX_PERCENT));
+			sp_std::vec![
+				(FeeRecipient::Treasury, treasury_cut),
+				(FeeRecipient::Author, treasury_cut.left_from_one()),
+			]
+		}
+
+		/// Shares in which tips are currently distributed
+		pub fn tip_shares() -> Vec<(FeeRecipientOf<T>, Perbill)> {
+			let recipients = Self::tip_recipients();
+			if !recipients.is_empty() {
+				return recipients.into_inner();
+			}
+
+			sp_std::vec![(FeeRecipient::Author, Perbill::one())]
+		}
+
+		/// Beneficiary of the approved registration of given contract
+		pub fn rebate_beneficiary(contract: &ContractAddressOf<T>) -> Option<T::AccountId> {
+			Self::contract_rebates(contract)
+				.filter(|rebate| rebate.approved)
+				.map(|rebate| rebate.beneficiary)
+		}
+
+		/// Remember contract called by given call, so that evm fees charged
+		/// while applying it can be rebated
+		pub fn note_called_contract(call: &<T as frame_system::Config>::RuntimeCall) {
+			<CurrentContract<T>>::set(T::CalledContract::called_contract(call));
+		}
+
+		/// Oldest ledger period that may still have entries
+		pub fn oldest_ledger_period() -> PeriodIndex {
+			<OldestLedgerPeriod<T>>::get()
+		}
+
+		/// Remember contract that `deployer` would create by dispatching given call.
+		/// Contracts that already exist are ignored, so their deployer can not be replaced
+		pub fn note_deployment(
+			call: &<T as frame_system::Config>::RuntimeCall,
+			deployer: &T::AccountId,
+		) {
+			if let Some(contract) = T::CalledContract::deployed_contract(call, deployer) {
+				Self::note_deployed_contract(contract, deployer.clone());
+			}
+		}
+
+		/// Remember contract that `deployer` would create in the transaction being applied
+		pub fn note_deployed_contract(contract: ContractAddressOf<T>, deployer: T::AccountId) {
+			if !T::CalledContract::contract_exists(&contract) {
+				<PendingDeployment<T>>::put((contract, deployer));
+			}
+		}
+
+		/// Record deployer of the contract noted before applying the transaction,
+		/// if the contract was actually created
+		pub fn record_deployment() {
+			if let Some((contract, deployer)) = <PendingDeployment<T>>::take() {
+				if T::CalledContract::contract_exists(&contract) {
+					<ContractDeployers<T>>::insert(contract, deployer);
+				}
+			}
+		}
+
+		/// Index of the ledger period containing given block
+		pub fn period_index(n: BlockNumberFor<T>) -> PeriodIndex {
+			let period = T::LedgerPeriod::get();
+			if period.is_zero() {
+				return 0;
+			}
+			(n / period).unique_saturated_into()
+		}
+
+		/// Add given amount to the ledger of recipient.
+		/// Fees are handled outside of any dispatchable, so the ledger writes
+		/// are registered as extra weight of the block
+		pub(crate) fn record(recipient: FeeRecipientOf<T>, kind: FeeKind, amount: BalanceOf<T>) {
+			if amount.is_zero() {
+				return;
+			}
+
+			let period = Self::period_index(<frame_system::Pallet<T>>::block_number());
+			<PeriodFees<T>>::mutate(period, &recipient, |totals| totals.record(kind, amount));
+			<CumulativeFees<T>>::mutate(recipient, |totals| totals.record(kind, amount));
+
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(
+				WeightInfo::<T>::record_fee(),
+				DispatchClass::Mandatory,
+			);
+		}
+
+		/// Remove ledger periods that are out of retention at block `n`,
+		/// oldest first. At most `limit` entries are removed, the removal
+		/// continues in next blocks from `LedgerRemovalCursor`
+		pub fn remove_expired_periods(n: BlockNumberFor<T>, limit: u32) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let retained = T::LedgerRetainedPeriods::get();
+			let current = Self::period_index(n);
+			let mut oldest = Self::oldest_ledger_period();
+			if oldest.saturating_add(retained) > current {
+				return db_weight.reads(1);
+			}
+
+			let mut cursor = <LedgerRemovalCursor<T>>::get();
+			let mut budget = limit;
+			let mut removed = 0_u64;
+			while budget > 0 && oldest.saturating_add(retained) <= current {
+				let result = <PeriodFees<T>>::clear_prefix(
+					oldest,
+					budget,
+					cursor.as_ref().map(|cursor| cursor.as_slice()),
+				);
+				removed = removed.saturating_add(result.unique as u64);
+				// Empty periods still cost a step so the loop is bounded
+				budget = budget.saturating_sub(result.loops.max(1));
+
+				match result.maybe_cursor {
+					Some(next) => {
+						// Raw key of `PeriodFees` always fits in the cursor bound
+						cursor = LedgerCursor::try_from(next).ok();
+						break;
+					}
+					None => {
+						oldest = oldest.saturating_add(1);
+						cursor = None;
+					}
+				}
+			}
+
+			<OldestLedgerPeriod<T>>::put(oldest);
+			<LedgerRemovalCursor<T>>::set(cursor);
+
+			db_weight.reads_writes(
+				2_u64.saturating_add((limit - budget) as u64),
+				2_u64.saturating_add(removed),
+			)
+		}
+
+		/// Retained ledger periods of recipient, oldest first
+		pub fn recent_period_fees(
+			recipient: FeeRecipientOf<T>,
+		) -> Vec<(PeriodIndex, FeeTotalsOf<T>)> {
+			let current = Self::period_index(<frame_system::Pallet<T>>::block_number());
+			let first = current.saturating_sub(T::LedgerRetainedPeriods::get().saturating_sub(1));
+			(first..=current)
+				.map(|period| (period, Self::period_fees(period, &recipient)))
+				.collect()
+		}
+	}
 }
 
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: Config> WeightInfo<T> {
 	pub fn set_config_with_u32() -> Weight {
-		Weight::from_ref_time(10_000_000).saturating_add(T::DbWeight::get().writes(1))
+		Weight::from_ref_time(10_000_000).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	pub fn set_fee_recipients(recipients: u32) -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(Weight::from_ref_time(250_000).saturating_mul(recipients as u64))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	pub fn set_tip_recipients(recipients: u32) -> Weight {
+		Weight::from_ref_time(12_000_000)
+			.saturating_add(Weight::from_ref_time(250_000).saturating_mul(recipients as u64))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	pub fn request_contract_rebate() -> Weight {
+		Weight::from_ref_time(25_000_000).saturating_add(T::DbWeight::get().reads_writes(3, 2))
+	}
+
+	pub fn force_register_contract_rebate() -> Weight {
+		Weight::from_ref_time(15_000_000).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	pub fn approve_contract_rebate() -> Weight {
+		Weight::from_ref_time(15_000_000).saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
+	pub fn remove_contract_rebate() -> Weight {
+		Weight::from_ref_time(25_000_000).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	/// Ledger of a single recipient, `PeriodFees` and `CumulativeFees` are mutated
+	pub fn record_fee() -> Weight {
+		Weight::from_ref_time(5_000_000).saturating_add(T::DbWeight::get().reads_writes(2, 2))
 	}
 }
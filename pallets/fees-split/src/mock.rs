@@ -0,0 +1,237 @@
+use crate::{self as pallet_fees_split, types::NegativeImbalanceOf, ContractAddress};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, ConstU8, Currency, GenesisBuild, OnUnbalanced},
+	weights::{IdentityFee, Weight},
+};
+use frame_system::EnsureRoot;
+use pallet_evm::AddressMapping;
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+pub type AccountId = u64;
+pub type Balance = u128;
+
+pub const TREASURY: AccountId = 1;
+pub const AUTHOR: AccountId = 2;
+pub const POT: AccountId = 3;
+pub const ALICE: AccountId = 10;
+pub const BOB: AccountId = 11;
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		EVM: pallet_evm::{Pallet, Call, Storage, Event<T>},
+		FeesSplit: pallet_fees_split::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+impl pallet_transaction_payment::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction = pallet_fees_split::ChargeTransactionFees<Test>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<Balance>;
+	type LengthToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+/// Evm address whose low bytes are the account id
+pub fn evm_address(account: AccountId) -> H160 {
+	H160::from_low_u64_be(account)
+}
+
+pub struct MockAddressMapping;
+
+impl AddressMapping<AccountId> for MockAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		address.to_low_u64_be()
+	}
+}
+
+parameter_types! {
+	pub WeightPerGas: Weight = Weight::from_ref_time(20_000);
+	pub BlockGasLimit: U256 = U256::from(u64::MAX);
+}
+
+impl pallet_evm::Config for Test {
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type CallOrigin = pallet_evm::EnsureAddressRoot<Self::AccountId>;
+	type WithdrawOrigin = pallet_evm::EnsureAddressNever<Self::AccountId>;
+	type AddressMapping = MockAddressMapping;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = ();
+	type PrecompilesValue = ();
+	type ChainId = ();
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = pallet_fees_split::DistributeEvmFees<Test>;
+	type FindAuthor = ();
+}
+
+pub struct Treasury;
+
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for Treasury {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+		Balances::resolve_creating(&TREASURY, amount);
+	}
+}
+
+pub struct Author;
+
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for Author {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+		Balances::resolve_creating(&AUTHOR, amount);
+	}
+}
+
+/// Remark of a single byte is treated as a call to the wasm contract with that id,
+/// and a remark of `[DEPLOY, id]` as deployment of it.
+/// Wasm contract exists as long as its account exists
+pub struct CalledContract;
+
+pub const DEPLOY: u8 = 0xde;
+
+impl pallet_fees_split::CalledContract<RuntimeCall, AccountId> for CalledContract {
+	fn called_contract(call: &RuntimeCall) -> Option<ContractAddress<AccountId>> {
+		match call {
+			RuntimeCall::System(frame_system::Call::remark { remark }) if remark.len() == 1 => {
+				Some(ContractAddress::Wasm(remark[0] as AccountId))
+			}
+			_ => None,
+		}
+	}
+
+	fn deployed_contract(
+		call: &RuntimeCall,
+		_deployer: &AccountId,
+	) -> Option<ContractAddress<AccountId>> {
+		match call {
+			RuntimeCall::System(frame_system::Call::remark { remark })
+				if remark.len() == 2 && remark[0] == DEPLOY =>
+			{
+				Some(ContractAddress::Wasm(remark[1] as AccountId))
+			}
+			_ => None,
+		}
+	}
+
+	fn contract_exists(contract: &ContractAddress<AccountId>) -> bool {
+		match contract {
+			ContractAddress::Wasm(account) => System::account_exists(account),
+			ContractAddress::Evm(_) => false,
+		}
+	}
+}
+
+parameter_types! {
+	pub storage CollatorPot: Option<AccountId> = None;
+}
+
+impl pallet_fees_split::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type Treasury = Treasury;
+	type Author = Author;
+	type CollatorPot = CollatorPot;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type CalledContract = CalledContract;
+	type RebateDeposit = ConstU128<100>;
+	type MaxRecipients = ConstU32<4>;
+	type LedgerPeriod = ConstU64<10>;
+	type LedgerRetainedPeriods = ConstU32<3>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	<pallet_fees_split::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(
+		&pallet_fees_split::GenesisConfig::new(80),
+		&mut storage,
+	)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Set fee recipients with shares given in whole percents
+pub fn set_shares(shares: Vec<(pallet_fees_split::FeeRecipient<AccountId>, u32)>) {
+	let shares = shares
+		.into_iter()
+		.map(|(recipient, percent)| (recipient, Perbill::from_percent(percent)))
+		.collect();
+	frame_support::assert_ok!(FeesSplit::set_fee_recipients(RuntimeOrigin::root(), shares));
+}
@@ -0,0 +1,619 @@
+use crate::{
+	contracts::create_address, mock::*, ChargeTransactionFees, ContractAddress, ContractDeployers,
+	CumulativeFees, CurrentContract, DistributeEvmFees, Error, Event, FeeKind, FeeRecipient,
+	FeeTotals, LedgerRemovalCursor, PeriodFees, WeightInfo, LEDGER_REMOVAL_LIMIT,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchClass, DispatchInfo, PostDispatchInfo},
+	traits::{Currency, Get, Hooks, ReservableCurrency},
+};
+use pallet_evm::OnChargeEVMTransaction;
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{DispatchError, Perbill};
+
+const DEVELOPER: AccountId = 20;
+const CONTRACT: AccountId = 7;
+
+fn fees(fees: Balance, tips: Balance, evm_fees: Balance) -> FeeTotals<Balance> {
+	FeeTotals {
+		fees,
+		tips,
+		evm_fees,
+	}
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		FeesSplit::on_initialize(System::block_number());
+	}
+}
+
+/// Charge substrate transaction fee and tip from `who` for given call,
+/// of which only `corrected_fee` is kept
+fn charge_transaction(
+	who: AccountId,
+	call: &RuntimeCall,
+	fee: Balance,
+	tip: Balance,
+	corrected_fee: Balance,
+) {
+	let info = DispatchInfo::default();
+	let paid = <ChargeTransactionFees<Test> as OnChargeTransaction<Test>>::withdraw_fee(
+		&who, call, &info, fee, tip,
+	)
+	.unwrap();
+	assert_ok!(
+		<ChargeTransactionFees<Test> as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+			&who,
+			&info,
+			&PostDispatchInfo::default(),
+			corrected_fee,
+			tip,
+			paid,
+		)
+	);
+}
+
+/// Charge evm transaction fee from `who`, of which only `corrected_fee` is kept
+fn charge_evm_transaction(who: AccountId, fee: u64, corrected_fee: u64, base_fee: u64) {
+	let address = evm_address(who);
+	let paid = <DistributeEvmFees<Test> as OnChargeEVMTransaction<Test>>::withdraw_fee(
+		&address,
+		fee.into(),
+	)
+	.unwrap();
+	let tip = <DistributeEvmFees<Test> as OnChargeEVMTransaction<Test>>::correct_and_deposit_fee(
+		&address,
+		corrected_fee.into(),
+		base_fee.into(),
+		paid,
+	);
+	<DistributeEvmFees<Test> as OnChargeEVMTransaction<Test>>::pay_priority_fee(tip);
+}
+
+fn register_rebate(contract: ContractAddress<AccountId>) {
+	<ContractDeployers<Test>>::insert(&contract, BOB);
+	assert_ok!(FeesSplit::request_contract_rebate(
+		RuntimeOrigin::signed(BOB),
+		contract.clone(),
+		DEVELOPER
+	));
+	assert_ok!(FeesSplit::approve_contract_rebate(
+		RuntimeOrigin::root(),
+		contract
+	));
+	assert_ok!(FeesSplit::set_rebate_share(
+		RuntimeOrigin::root(),
+		Perbill::from_percent(10)
+	));
+}
+
+#[test]
+fn treasury_cut_bounds() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(FeesSplit::treasury_cut_percent(), 80);
+
+		assert_noop!(
+			FeesSplit::set_treasury_cut_percent(RuntimeOrigin::signed(ALICE), 50),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			FeesSplit::set_treasury_cut_percent(RuntimeOrigin::root(), 101),
+			Error::<Test>::InvalidTreasuryCut
+		);
+
+		assert_ok!(FeesSplit::set_treasury_cut_percent(
+			RuntimeOrigin::root(),
+			100
+		));
+		System::assert_last_event(Event::TreasuryCutChanged { old: 80, new: 100 }.into());
+		assert_eq!(
+			FeesSplit::fee_shares(),
+			vec![
+				(FeeRecipient::Treasury, Perbill::one()),
+				(FeeRecipient::Author, Perbill::zero()),
+			]
+		);
+	});
+}
+
+#[test]
+fn validate_recipients() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeesSplit::set_fee_recipients(
+				RuntimeOrigin::root(),
+				vec![
+					(FeeRecipient::Treasury, Perbill::from_percent(50)),
+					(FeeRecipient::Author, Perbill::from_percent(40)),
+				]
+			),
+			Error::<Test>::InvalidShares
+		);
+		assert_noop!(
+			FeesSplit::set_tip_recipients(
+				RuntimeOrigin::root(),
+				vec![(FeeRecipient::Burn, Perbill::from_percent(20)); 5]
+			),
+			Error::<Test>::TooManyRecipients
+		);
+
+		// Empty list falls back to the default shares
+		assert_ok!(FeesSplit::set_fee_recipients(RuntimeOrigin::root(), vec![]));
+		assert_eq!(
+			FeesSplit::fee_shares(),
+			vec![
+				(FeeRecipient::Treasury, Perbill::from_percent(80)),
+				(FeeRecipient::Author, Perbill::from_percent(20)),
+			]
+		);
+		assert_eq!(
+			FeesSplit::tip_shares(),
+			vec![(FeeRecipient::Author, Perbill::one())]
+		);
+	});
+}
+
+#[test]
+fn split_by_treasury_cut() {
+	new_test_ext().execute_with(|| {
+		FeesSplit::distribute_fee(Balances::issue(1_000), FeeKind::Fee, None);
+
+		assert_eq!(Balances::free_balance(TREASURY), 800);
+		assert_eq!(Balances::free_balance(AUTHOR), 200);
+	});
+}
+
+#[test]
+fn split_by_recipient_shares() {
+	new_test_ext().execute_with(|| {
+		set_shares(vec![
+			(FeeRecipient::Treasury, 33),
+			(FeeRecipient::Account(DEVELOPER), 33),
+			(FeeRecipient::Author, 34),
+		]);
+		System::assert_last_event(
+			Event::FeeRecipientsChanged {
+				recipients: FeesSplit::fee_recipients(),
+			}
+			.into(),
+		);
+
+		// Last recipient receive the remainder left by rounding
+		FeesSplit::distribute_fee(Balances::issue(1_001), FeeKind::Fee, None);
+
+		assert_eq!(Balances::free_balance(TREASURY), 330);
+		assert_eq!(Balances::free_balance(DEVELOPER), 330);
+		assert_eq!(Balances::free_balance(AUTHOR), 341);
+	});
+}
+
+#[test]
+fn burn_and_collator_pot() {
+	new_test_ext().execute_with(|| {
+		set_shares(vec![
+			(FeeRecipient::Burn, 50),
+			(FeeRecipient::CollatorPot, 50),
+		]);
+		let issuance = Balances::total_issuance();
+
+		// Pot share goes to treasury when chain have no pot
+		FeesSplit::distribute_fee(Balances::issue(1_000), FeeKind::Fee, None);
+		assert_eq!(Balances::total_issuance(), issuance + 500);
+		assert_eq!(Balances::free_balance(TREASURY), 500);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Treasury),
+			fees(500, 0, 0)
+		);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Burn),
+			fees(500, 0, 0)
+		);
+
+		CollatorPot::set(&Some(POT));
+		FeesSplit::distribute_fee(Balances::issue(1_000), FeeKind::Fee, None);
+		assert_eq!(Balances::free_balance(POT), 500);
+		assert_eq!(Balances::free_balance(TREASURY), 500);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::CollatorPot),
+			fees(500, 0, 0)
+		);
+	});
+}
+
+#[test]
+fn substrate_transaction_fees() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(FeesSplit::set_tip_recipients(
+			RuntimeOrigin::root(),
+			vec![
+				(FeeRecipient::Author, Perbill::from_percent(50)),
+				(FeeRecipient::Treasury, Perbill::from_percent(50)),
+			]
+		));
+
+		// 400 of the fee is refunded, tip is always kept
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		charge_transaction(ALICE, &call, 1_000, 100, 600);
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 600);
+		assert_eq!(Balances::free_balance(TREASURY), 400 + 50);
+		assert_eq!(Balances::free_balance(AUTHOR), 100 + 50);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Treasury),
+			fees(400, 50, 0)
+		);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Author),
+			fees(100, 50, 0)
+		);
+		assert_eq!(<CurrentContract<Test>>::get(), None);
+	});
+}
+
+#[test]
+fn evm_transaction_fees() {
+	new_test_ext().execute_with(|| {
+		// 300 of the fee is refunded, priority fee is given to author as a tip
+		charge_evm_transaction(ALICE, 1_000, 700, 500);
+
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 700);
+		assert_eq!(Balances::free_balance(TREASURY), 400);
+		assert_eq!(Balances::free_balance(AUTHOR), 100 + 200);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Treasury),
+			fees(0, 0, 400)
+		);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Author),
+			fees(0, 0, 300)
+		);
+
+		// Nothing is charged for zero fee
+		charge_evm_transaction(BOB, 0, 0, 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn ledger_periods() {
+	new_test_ext().execute_with(|| {
+		// Periods are 10 blocks long and last 3 of them are kept
+		FeesSplit::distribute_fee(Balances::issue(100), FeeKind::Fee, None);
+		run_to_block(10);
+		FeesSplit::distribute_fee(Balances::issue(200), FeeKind::Tip, None);
+		run_to_block(20);
+		FeesSplit::distribute_fee(Balances::issue(300), FeeKind::Evm, None);
+
+		assert_eq!(FeesSplit::period_index(19), 1);
+		assert_eq!(
+			FeesSplit::period_fees(0, FeeRecipient::Treasury),
+			fees(80, 0, 0)
+		);
+		assert_eq!(
+			FeesSplit::recent_period_fees(FeeRecipient::Treasury),
+			vec![
+				(0, fees(80, 0, 0)),
+				(1, fees(0, 160, 0)),
+				(2, fees(0, 0, 240)),
+			]
+		);
+
+		// First block of period 3 drops period 0, cumulative totals are kept
+		run_to_block(30);
+		assert!(!<PeriodFees<Test>>::contains_key(0, FeeRecipient::Treasury));
+		assert!(!<PeriodFees<Test>>::contains_key(0, FeeRecipient::Author));
+		assert_eq!(
+			FeesSplit::recent_period_fees(FeeRecipient::Treasury),
+			vec![
+				(1, fees(0, 160, 0)),
+				(2, fees(0, 0, 240)),
+				(3, fees(0, 0, 0)),
+			]
+		);
+		assert_eq!(
+			<CumulativeFees<Test>>::get(FeeRecipient::Treasury),
+			fees(80, 160, 240)
+		);
+	});
+}
+
+#[test]
+fn expired_period_removal_is_bounded() {
+	new_test_ext().execute_with(|| {
+		let recipients = LEDGER_REMOVAL_LIMIT as u64 + 50;
+		for account in 0..recipients {
+			<PeriodFees<Test>>::insert(0, FeeRecipient::Account(100 + account), fees(1, 0, 0));
+		}
+		let period_entries = |period| <PeriodFees<Test>>::iter_prefix(period).count() as u64;
+
+		// Only part of the expired period is removed in a block
+		run_to_block(30);
+		assert_eq!(period_entries(0), 50);
+		assert_eq!(FeesSplit::oldest_ledger_period(), 0);
+		assert!(<LedgerRemovalCursor<Test>>::get().is_some());
+
+		run_to_block(31);
+		assert_eq!(period_entries(0), 0);
+		assert_eq!(FeesSplit::oldest_ledger_period(), 1);
+		assert_eq!(<LedgerRemovalCursor<Test>>::get(), None);
+
+		// Nothing left to remove until next period expires
+		assert_eq!(
+			FeesSplit::remove_expired_periods(39, LEDGER_REMOVAL_LIMIT),
+			<Test as frame_system::Config>::DbWeight::get().reads(1)
+		);
+	});
+}
+
+#[test]
+fn ledger_writes_are_weighed() {
+	new_test_ext().execute_with(|| {
+		let mandatory_weight = || *System::block_weight().get(DispatchClass::Mandatory);
+		let before = mandatory_weight();
+
+		FeesSplit::distribute_fee(Balances::issue(1_000), FeeKind::Fee, None);
+
+		// Treasury and author ledgers are written
+		assert_eq!(
+			mandatory_weight(),
+			before.saturating_add(WeightInfo::<Test>::record_fee().saturating_mul(2))
+		);
+	});
+}
+
+#[test]
+fn rebate_registration() {
+	new_test_ext().execute_with(|| {
+		let contract = ContractAddress::Wasm(CONTRACT);
+		<ContractDeployers<Test>>::insert(&contract, BOB);
+
+		assert_noop!(
+			FeesSplit::request_contract_rebate(
+				RuntimeOrigin::signed(ALICE),
+				contract.clone(),
+				ALICE
+			),
+			Error::<Test>::NotContractDeployer
+		);
+		assert_ok!(FeesSplit::request_contract_rebate(
+			RuntimeOrigin::signed(BOB),
+			contract.clone(),
+			DEVELOPER
+		));
+		System::assert_last_event(
+			Event::RebateRequested {
+				contract: contract.clone(),
+				requester: BOB,
+				beneficiary: DEVELOPER,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(FeesSplit::rebate_beneficiary(&contract), None);
+
+		assert_noop!(
+			FeesSplit::request_contract_rebate(
+				RuntimeOrigin::signed(ALICE),
+				contract.clone(),
+				ALICE
+			),
+			Error::<Test>::RebateAlreadyRegistered
+		);
+		assert_noop!(
+			FeesSplit::approve_contract_rebate(RuntimeOrigin::signed(BOB), contract.clone()),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(FeesSplit::approve_contract_rebate(
+			RuntimeOrigin::root(),
+			contract.clone()
+		));
+		assert_eq!(FeesSplit::rebate_beneficiary(&contract), Some(DEVELOPER));
+		assert_noop!(
+			FeesSplit::approve_contract_rebate(RuntimeOrigin::root(), contract.clone()),
+			Error::<Test>::RebateAlreadyApproved
+		);
+
+		assert_noop!(
+			FeesSplit::remove_contract_rebate(RuntimeOrigin::signed(ALICE), contract.clone()),
+			Error::<Test>::NotRebateRequester
+		);
+		assert_ok!(FeesSplit::remove_contract_rebate(
+			RuntimeOrigin::signed(BOB),
+			contract.clone()
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_noop!(
+			FeesSplit::remove_contract_rebate(RuntimeOrigin::root(), contract),
+			Error::<Test>::RebateNotRegistered
+		);
+	});
+}
+
+#[test]
+fn rebate_of_substrate_fees() {
+	new_test_ext().execute_with(|| {
+		register_rebate(ContractAddress::Wasm(CONTRACT));
+
+		let call = RuntimeCall::System(frame_system::Call::remark {
+			remark: vec![CONTRACT as u8],
+		});
+		charge_transaction(ALICE, &call, 1_000, 0, 1_000);
+
+		assert_eq!(Balances::free_balance(DEVELOPER), 100);
+		assert_eq!(Balances::free_balance(TREASURY), 720);
+		assert_eq!(Balances::free_balance(AUTHOR), 180);
+		assert_eq!(
+			FeesSplit::cumulative_fees(FeeRecipient::Account(DEVELOPER)),
+			fees(100, 0, 0)
+		);
+	});
+}
+
+#[test]
+fn rebate_of_evm_fees() {
+	new_test_ext().execute_with(|| {
+		let contract = ContractAddress::Evm(evm_address(CONTRACT));
+		register_rebate(contract.clone());
+
+		// Only the base fee is subject to rebates
+		<CurrentContract<Test>>::put(contract);
+		charge_evm_transaction(ALICE, 1_000, 1_000, 500);
+
+		assert_eq!(Balances::free_balance(DEVELOPER), 50);
+		assert_eq!(Balances::free_balance(TREASURY), 360);
+		assert_eq!(Balances::free_balance(AUTHOR), 90 + 500);
+		assert_eq!(<CurrentContract<Test>>::get(), None);
+	});
+}
+
+#[test]
+fn called_contract_is_forgotten_without_evm_fee() {
+	new_test_ext().execute_with(|| {
+		let contract = ContractAddress::Evm(evm_address(CONTRACT));
+		register_rebate(contract.clone());
+
+		<CurrentContract<Test>>::put(contract);
+		charge_evm_transaction(ALICE, 0, 0, 0);
+		assert_eq!(<CurrentContract<Test>>::get(), None);
+
+		// Next transaction is not attributed to the contract
+		charge_evm_transaction(ALICE, 1_000, 1_000, 1_000);
+		assert_eq!(Balances::free_balance(DEVELOPER), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 800);
+	});
+}
+
+#[test]
+fn unapproved_rebate_is_not_paid() {
+	new_test_ext().execute_with(|| {
+		let contract = ContractAddress::Wasm(CONTRACT);
+		<ContractDeployers<Test>>::insert(&contract, BOB);
+		assert_ok!(FeesSplit::request_contract_rebate(
+			RuntimeOrigin::signed(BOB),
+			contract.clone(),
+			DEVELOPER
+		));
+		assert_ok!(FeesSplit::set_rebate_share(
+			RuntimeOrigin::root(),
+			Perbill::from_percent(10)
+		));
+
+		FeesSplit::distribute_fee(Balances::issue(1_000), FeeKind::Fee, Some(contract.clone()));
+		assert_eq!(Balances::free_balance(DEVELOPER), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 800);
+
+		// Root can reject the request, deposit is given back to requester
+		assert_ok!(FeesSplit::remove_contract_rebate(
+			RuntimeOrigin::root(),
+			contract.clone()
+		));
+		System::assert_last_event(Event::RebateRemoved { contract }.into());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn deployments_are_recorded() {
+	new_test_ext().execute_with(|| {
+		let contract = ContractAddress::Wasm(CONTRACT);
+		let deploy = RuntimeCall::System(frame_system::Call::remark {
+			remark: vec![DEPLOY, CONTRACT as u8],
+		});
+
+		// Deployment that fails to create the contract is not recorded
+		charge_transaction(BOB, &deploy, 1_000, 0, 1_000);
+		assert_eq!(FeesSplit::contract_deployer(&contract), None);
+
+		// Contract comes into existence while the deployment is dispatched
+		let info = DispatchInfo::default();
+		let paid = <ChargeTransactionFees<Test> as OnChargeTransaction<Test>>::withdraw_fee(
+			&BOB, &deploy, &info, 1_000, 0,
+		)
+		.unwrap();
+		Balances::make_free_balance_be(&CONTRACT, 1_000);
+		assert_ok!(
+			<ChargeTransactionFees<Test> as OnChargeTransaction<Test>>::correct_and_deposit_fee(
+				&BOB,
+				&info,
+				&PostDispatchInfo::default(),
+				1_000,
+				0,
+				paid,
+			)
+		);
+		assert_eq!(FeesSplit::contract_deployer(&contract), Some(BOB));
+
+		// Deploying to the address of an existing contract does not change its deployer
+		charge_transaction(ALICE, &deploy, 1_000, 0, 1_000);
+		assert_eq!(FeesSplit::contract_deployer(&contract), Some(BOB));
+
+		assert_ok!(FeesSplit::request_contract_rebate(
+			RuntimeOrigin::signed(BOB),
+			contract,
+			DEVELOPER
+		));
+	});
+}
+
+#[test]
+fn force_rebate_registration() {
+	new_test_ext().execute_with(|| {
+		let contract = ContractAddress::Wasm(CONTRACT);
+
+		assert_noop!(
+			FeesSplit::force_register_contract_rebate(
+				RuntimeOrigin::signed(BOB),
+				contract.clone(),
+				DEVELOPER
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(FeesSplit::force_register_contract_rebate(
+			RuntimeOrigin::root(),
+			contract.clone(),
+			DEVELOPER
+		));
+		System::assert_last_event(
+			Event::RebateApproved {
+				contract: contract.clone(),
+			}
+			.into(),
+		);
+		assert_eq!(FeesSplit::rebate_beneficiary(&contract), Some(DEVELOPER));
+		assert_eq!(Balances::reserved_balance(DEVELOPER), 0);
+
+		assert_noop!(
+			FeesSplit::force_register_contract_rebate(
+				RuntimeOrigin::root(),
+				contract.clone(),
+				ALICE
+			),
+			Error::<Test>::RebateAlreadyRegistered
+		);
+
+		// Beneficiary can give up the registration as its requester
+		assert_ok!(FeesSplit::remove_contract_rebate(
+			RuntimeOrigin::signed(DEVELOPER),
+			contract.clone()
+		));
+		assert_eq!(FeesSplit::rebate_beneficiary(&contract), None);
+	});
+}
+
+#[test]
+fn evm_create_address() {
+	let source: sp_core::H160 =
+		hex_literal::hex!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").into();
+	assert_eq!(
+		create_address(source, 0.into()),
+		hex_literal::hex!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d").into()
+	);
+	assert_eq!(
+		create_address(source, 1.into()),
+		hex_literal::hex!("343c43a37d37dff08ae8c4a11544c718abb4fcf8").into()
+	);
+}
@@ -0,0 +1,81 @@
+use crate::types::{AccountIdOf, BalanceOf, ContractAddressOf, FeeKind, NegativeImbalanceOf};
+use crate::{Config, CurrentContract, Pallet};
+use frame_support::traits::{Currency, ExistenceRequirement, Imbalance, WithdrawReasons};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+use sp_std::marker::PhantomData;
+
+type PositiveImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::PositiveImbalance;
+
+/// `OnChargeTransaction` implementation that behaves like `CurrencyAdapter`
+/// distributing to the configured recipients, but also remembers the contract
+/// called by the transaction so that its fee is subject to rebates
+pub struct ChargeTransactionFees<T>(PhantomData<T>);
+
+impl<T> OnChargeTransaction<T> for ChargeTransactionFees<T>
+where
+	T: Config + pallet_transaction_payment::Config,
+{
+	type Balance = BalanceOf<T>;
+	type LiquidityInfo = Option<(NegativeImbalanceOf<T>, Option<ContractAddressOf<T>>)>;
+
+	fn withdraw_fee(
+		who: &AccountIdOf<T>,
+		call: &<T as frame_system::Config>::RuntimeCall,
+		_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		// Evm fees charged while dispatching the call are rebated as well
+		Pallet::<T>::note_called_contract(call);
+		Pallet::<T>::note_deployment(call, who);
+
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let withdraw_reason = if tip.is_zero() {
+			WithdrawReasons::TRANSACTION_PAYMENT
+		} else {
+			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
+		};
+
+		match T::Currency::withdraw(who, fee, withdraw_reason, ExistenceRequirement::KeepAlive) {
+			Ok(imbalance) => Ok(Some((imbalance, <CurrentContract<T>>::get()))),
+			Err(_) => Err(InvalidTransaction::Payment.into()),
+		}
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountIdOf<T>,
+		_dispatch_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_post_info: &PostDispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		<CurrentContract<T>>::kill();
+		Pallet::<T>::record_deployment();
+
+		if let Some((paid, contract)) = already_withdrawn {
+			// Refund the difference between estimated and actual fee
+			let refund_amount = paid.peek().saturating_sub(corrected_fee);
+			let refund_imbalance = T::Currency::deposit_into_existing(who, refund_amount)
+				.unwrap_or_else(|_| PositiveImbalanceOf::<T>::zero());
+			let adjusted_paid = paid
+				.offset(refund_imbalance)
+				.same()
+				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+
+			let (tip, fee) = adjusted_paid.split(tip);
+			Pallet::<T>::distribute_fee(fee, FeeKind::Fee, contract);
+			Pallet::<T>::distribute(tip, Pallet::<T>::tip_shares(), FeeKind::Tip);
+		}
+
+		Ok(())
+	}
+}
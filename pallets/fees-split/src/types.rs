@@ -0,0 +1,142 @@
+use crate::Config;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::Currency, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::H160;
+use sp_runtime::{traits::Saturating, Perbill};
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
+
+pub type NegativeImbalanceOf<T> =
+	<<T as Config>::Currency as Currency<AccountIdOf<T>>>::NegativeImbalance;
+
+/// Party that receive a share of collected fees
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum FeeRecipient<AccountId> {
+	/// Treasury of the chain
+	Treasury,
+	/// Author of the block in which fee was paid
+	Author,
+	/// Share is removed from total issuance
+	Burn,
+	/// Reward pot of collators
+	CollatorPot,
+	/// Any other account
+	Account(AccountId),
+}
+
+pub type FeeRecipientOf<T> = FeeRecipient<AccountIdOf<T>>;
+
+pub type FeeRecipientsOf<T> =
+	BoundedVec<(FeeRecipientOf<T>, Perbill), <T as Config>::MaxRecipients>;
+
+/// Origin of an amount distributed by this pallet
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FeeKind {
+	/// Fee of substrate transaction
+	Fee,
+	/// Tip of substrate transaction
+	Tip,
+	/// Base and priority fee of evm transaction
+	Evm,
+}
+
+/// Amounts received by a recipient, separated by their origin
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeTotals<Balance> {
+	/// Share of substrate transaction fees
+	pub fees: Balance,
+	/// Share of substrate transaction tips
+	pub tips: Balance,
+	/// Share of evm transaction fees
+	pub evm_fees: Balance,
+}
+
+impl<Balance: Saturating + Copy> FeeTotals<Balance> {
+	/// Add given amount to the total of given kind
+	pub fn record(&mut self, kind: FeeKind, amount: Balance) {
+		let total = match kind {
+			FeeKind::Fee => &mut self.fees,
+			FeeKind::Tip => &mut self.tips,
+			FeeKind::Evm => &mut self.evm_fees,
+		};
+		*total = total.saturating_add(amount);
+	}
+}
+
+impl<Balance> FeeTotals<Balance> {
+	/// Convert every total with given function
+	pub fn map<R>(self, f: impl Fn(Balance) -> R) -> FeeTotals<R> {
+		FeeTotals {
+			fees: f(self.fees),
+			tips: f(self.tips),
+			evm_fees: f(self.evm_fees),
+		}
+	}
+}
+
+pub type FeeTotalsOf<T> = FeeTotals<BalanceOf<T>>;
+
+/// Index of a fee ledger period
+pub type PeriodIndex = u32;
+
+/// Raw storage key from where removal of an expired ledger period continues
+pub type LedgerCursor = BoundedVec<u8, frame_support::traits::ConstU32<256>>;
+
+/// Address of a smart contract, either deployed with `pallet_contracts` or `pallet_evm`
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ContractAddress<AccountId> {
+	/// Wasm contract of `pallet_contracts`
+	Wasm(AccountId),
+	/// Solidity contract of `pallet_evm`
+	Evm(H160),
+}
+
+pub type ContractAddressOf<T> = ContractAddress<AccountIdOf<T>>;
+
+/// Registration of a contract for developer fee rebates
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ContractRebate<AccountId, Balance> {
+	/// Account that requested the registration and reserved the deposit
+	pub requester: AccountId,
+	/// Account receiving the rebate
+	pub beneficiary: AccountId,
+	/// Amount reserved from requester until registration is removed
+	pub deposit: Balance,
+	/// Rebates are only paid once governance approved the registration
+	pub approved: bool,
+}
+
+pub type ContractRebateOf<T> = ContractRebate<AccountIdOf<T>, BalanceOf<T>>;
+
+/// Find the contract called by a runtime call, if any
+pub trait CalledContract<Call, AccountId> {
+	fn called_contract(call: &Call) -> Option<ContractAddress<AccountId>>;
+
+	/// Contract that `deployer` would create by dispatching given call, if any
+	fn deployed_contract(
+		_call: &Call,
+		_deployer: &AccountId,
+	) -> Option<ContractAddress<AccountId>> {
+		None
+	}
+
+	/// Whether given contract currently exists
+	fn contract_exists(_contract: &ContractAddress<AccountId>) -> bool {
+		false
+	}
+}
+
+impl<Call, AccountId> CalledContract<Call, AccountId> for () {
+	fn called_contract(_call: &Call) -> Option<ContractAddress<AccountId>> {
+		None
+	}
+}
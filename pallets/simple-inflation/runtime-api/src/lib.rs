@@ -0,0 +1,24 @@
+//! Runtime API definition for the simple inflation pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::Perbill;
+
+sp_api::decl_runtime_apis! {
+	pub trait InflationApi<Balance> where
+		Balance: Codec,
+	{
+		/// Annual inflation rate at current block
+		fn current_rate() -> Perbill;
+
+		/// Amount minted in the next minting block
+		fn next_issuance() -> Balance;
+
+		/// Amount minted during a year at current rate
+		fn projected_annual_issuance() -> Balance;
+
+		/// Total amount ever minted by the pallet
+		fn cumulative_minted() -> Balance;
+	}
+}
@@ -0,0 +1,88 @@
+//! Benchmarking setup for pallet-simple-inflation
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::Hooks;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, One};
+
+fn sample_model() -> InflationModel {
+	InflationModel {
+		annual_rate: Perbill::from_percent(10),
+		yearly_decay: Some(Perbill::from_percent(15)),
+		floor: Perbill::from_percent(2),
+	}
+}
+
+fn sample_beneficiaries<T: Config>(b: u32) -> Vec<(InflationBeneficiaryOf<T>, Perbill)> {
+	let share = Perbill::from_rational(1, b.max(1));
+	let mut beneficiaries = (0..b)
+		.map(|index| {
+			let who: AccountIdOf<T> = account("beneficiary", index, 0);
+			(InflationBeneficiary::Account(who), share)
+		})
+		.collect::<Vec<_>>();
+
+	// Last beneficiary take the rounding remainder so that shares sum up to 100%
+	if let Some((_, last)) = beneficiaries.last_mut() {
+		let others = share.deconstruct().saturating_mul(b.saturating_sub(1));
+		*last = Perbill::from_parts(Perbill::one().deconstruct().saturating_sub(others));
+	}
+
+	beneficiaries
+}
+
+benchmarks! {
+	on_initialize_accumulate {
+		<Inflation<T>>::put(sample_model());
+		let now = T::MintingPeriod::get().saturating_add(One::one());
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: {
+		Pallet::<T>::on_initialize(now);
+	}
+
+	on_initialize_mint {
+		let b in 1 .. T::MaxBeneficiaries::get();
+
+		<Inflation<T>>::put(sample_model());
+		<PendingIssuance<T>>::put(T::IssuingAmount::get());
+		<MaxTotalIssuance<T>>::put(BalanceOf::<T>::max_value());
+		// Cap reached by the previous minting is cleared again
+		<CapReached<T>>::put(true);
+		let beneficiaries: BeneficiariesOf<T> = sample_beneficiaries::<T>(b)
+			.try_into()
+			.expect("Within MaxBeneficiaries");
+		<Beneficiaries<T>>::put(beneficiaries);
+
+		let now = T::MintingPeriod::get();
+		frame_system::Pallet::<T>::set_block_number(now);
+	}: {
+		Pallet::<T>::on_initialize(now);
+	}
+
+	set_issuing_amount {
+	}: _(RawOrigin::Root, T::IssuingAmount::get())
+
+	set_inflation_model {
+	}: _(RawOrigin::Root, Some(sample_model()))
+
+	set_beneficiaries {
+		let b in 0 .. T::MaxBeneficiaries::get();
+		let beneficiaries = sample_beneficiaries::<T>(b);
+	}: _(RawOrigin::Root, beneficiaries)
+
+	set_max_total_issuance {
+	}: _(RawOrigin::Root, Some(BalanceOf::<T>::max_value()))
+
+	pause {
+	}: _(RawOrigin::Root)
+
+	resume {
+		<Paused<T>>::put(true);
+	}: _(RawOrigin::Root)
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
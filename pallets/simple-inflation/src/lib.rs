@@ -4,14 +4,47 @@
 pub use pallet::*;
 
 use frame_support::{
-	traits::{Currency, Get},
+	traits::{Currency, Get, Imbalance},
 	weights::Weight,
 };
-use sp_std::marker::PhantomData;
+use sp_runtime::{
+	traits::{One, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+	Perbill,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// All the types used in this pallet
+pub mod types;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// Weight Information related to this pallet
+pub mod weights;
 
-/// We only need to issue inflation to treasury, this will be always set to TreasuryPalletId
+pub use types::{InflationBeneficiary, InflationModel};
+use weights::WeightInfo;
+
+/// Length of a year used to derive per block issuance from annual rate
+pub const MILLISECS_PER_YEAR: u64 = 1000 * 60 * 60 * 24 * 36525 / 100;
+
+/// Handlers of the minted inflation shares.
+/// Pots that a chain does not have fall back to treasury
 pub trait Beneficiary<Imbalance> {
 	fn treasury(reward: Imbalance);
+
+	fn collator_pot(reward: Imbalance) {
+		Self::treasury(reward)
+	}
+
+	fn airdrop_pot(reward: Imbalance) {
+		Self::treasury(reward)
+	}
 }
 
 #[frame_support::pallet]
@@ -27,12 +60,23 @@ pub mod pallet {
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub issuing_amount: BalanceOf<T>,
+		#[cfg_attr(feature = "std", serde(default))]
+		pub inflation_model: Option<InflationModel>,
+		#[cfg_attr(feature = "std", serde(default))]
+		pub beneficiaries: Vec<(InflationBeneficiaryOf<T>, Perbill)>,
+		#[cfg_attr(feature = "std", serde(default))]
+		pub max_total_issuance: Option<BalanceOf<T>>,
 	}
 
 	pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 
 	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<AccountIdOf<T>>>::Balance;
 
+	pub type InflationBeneficiaryOf<T> = InflationBeneficiary<AccountIdOf<T>>;
+
+	pub type BeneficiariesOf<T> =
+		BoundedVec<(InflationBeneficiaryOf<T>, Perbill), <T as Config>::MaxBeneficiaries>;
+
 	pub(crate) type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
 		<T as frame_system::Config>::AccountId,
 	>>::NegativeImbalance;
@@ -46,10 +90,95 @@ pub mod pallet {
 	#[pallet::getter(fn issuing_amount)]
 	pub type IssuingAmount<T> = StorageValue<_, BalanceOf<T>, ValueQuery, DefaultIssuingAmount<T>>;
 
+	/// Percentage of supply inflation model.
+	/// When not set, fixed `IssuingAmount` is minted every block
+	#[pallet::storage]
+	#[pallet::getter(fn inflation_model)]
+	pub type Inflation<T> = StorageValue<_, InflationModel>;
+
+	/// Block since which years of the inflation model decay are counted
+	#[pallet::storage]
+	#[pallet::getter(fn inflation_start)]
+	pub type InflationStart<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Destinations of minted inflation with their share.
+	/// When empty, everything is given to treasury
+	#[pallet::storage]
+	#[pallet::getter(fn beneficiaries)]
+	pub type Beneficiaries<T: Config> = StorageValue<_, BeneficiariesOf<T>, ValueQuery>;
+
+	/// Total issuance is never increased above this amount by minting
+	#[pallet::storage]
+	#[pallet::getter(fn max_total_issuance)]
+	pub type MaxTotalIssuance<T> = StorageValue<_, BalanceOf<T>>;
+
+	/// Amount owed for the blocks of current minting period
+	#[pallet::storage]
+	#[pallet::getter(fn pending_issuance)]
+	pub type PendingIssuance<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Whether minting is temporarily paused
+	#[pallet::storage]
+	#[pallet::getter(fn paused)]
+	pub type Paused<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// Total amount ever minted by this pallet
+	#[pallet::storage]
+	#[pallet::getter(fn cumulative_minted)]
+	pub type CumulativeMinted<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Whether `MaxTotalIssuance` limited the last minting
+	#[pallet::storage]
+	#[pallet::getter(fn cap_reached)]
+	pub type CapReached<T> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Floor of inflation model is above its annual rate
+		InvalidInflationModel,
+		/// More beneficiaries than `MaxBeneficiaries` were given
+		TooManyBeneficiaries,
+		/// Shares of beneficiaries does not sum up to 100%
+		InvalidShares,
+		/// Minting is already paused
+		AlreadyPaused,
+		/// Minting is not paused
+		NotPaused,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Inflation model have been changed
+		InflationModelChanged { model: Option<InflationModel> },
+		/// Beneficiaries of inflation have been changed
+		BeneficiariesChanged { beneficiaries: BeneficiariesOf<T> },
+		/// Inflation minted in a distribution period have been distributed
+		InflationDistributed { amount: BalanceOf<T> },
+		/// Share of minted inflation have been issued to beneficiary
+		Issued {
+			amount: BalanceOf<T>,
+			beneficiary: InflationBeneficiaryOf<T>,
+		},
+		/// Maximum total issuance have been changed
+		MaxTotalIssuanceChanged { cap: Option<BalanceOf<T>> },
+		/// Total issuance reached the cap and minting stopped
+		MaxTotalIssuanceReached { total_issuance: BalanceOf<T> },
+		/// Minting have been paused
+		MintingPaused,
+		/// Minting have been resumed
+		MintingResumed,
+	}
+
 	#[cfg(feature = "std")]
 	impl<T: Config> GenesisConfig<T> {
 		pub fn new(issuing_amount: BalanceOf<T>) -> Self {
-			Self { issuing_amount }
+			Self {
+				issuing_amount,
+				inflation_model: None,
+				beneficiaries: Vec::new(),
+				max_total_issuance: None,
+			}
 		}
 	}
 
@@ -58,50 +187,369 @@ pub mod pallet {
 		fn default() -> Self {
 			Self {
 				issuing_amount: T::IssuingAmount::get(),
+				inflation_model: None,
+				beneficiaries: Vec::new(),
+				max_total_issuance: None,
 			}
 		}
 	}
 
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-		fn build(&self) {}
+		fn build(&self) {
+			if let Some(model) = self.inflation_model {
+				assert!(
+					model.floor <= model.annual_rate,
+					"Invalid inflation model in genesis"
+				);
+				<Inflation<T>>::put(model);
+			}
+
+			let beneficiaries = Pallet::<T>::validate_beneficiaries(self.beneficiaries.clone())
+				.expect("Invalid inflation beneficiaries in genesis");
+			<Beneficiaries<T>>::put(beneficiaries);
+
+			<MaxTotalIssuance<T>>::set(self.max_total_issuance);
+		}
 	}
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		type Currency: Currency<Self::AccountId>;
 
 		type IssuingAmount: Get<BalanceOf<Self>>;
 
+		/// Expected time between two blocks in milliseconds
+		#[pallet::constant]
+		type ExpectedBlockTime: Get<u64>;
+
 		type Beneficiary: Beneficiary<NegativeImbalanceOf<Self>>;
+
+		/// Maximum number of inflation beneficiaries
+		#[pallet::constant]
+		type MaxBeneficiaries: Get<u32>;
+
+		/// Number of blocks between two mintings.
+		/// Amount owed for every block of the period is minted at once
+		#[pallet::constant]
+		type MintingPeriod: Get<Self::BlockNumber>;
+
+		/// Origin allowed to change the inflation configuration
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics and hooks of this pallet
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: T::BlockNumber) -> Weight {
-			let inflation = T::Currency::issue(<IssuingAmount<T>>::get());
-			T::Beneficiary::treasury(inflation);
-			Weight::zero()
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if Self::paused() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let owed = Self::pending_issuance().saturating_add(Self::block_issuance(now));
+			if !Self::is_minting_block(now) {
+				<PendingIssuance<T>>::put(owed);
+				return T::WeightInfo::on_initialize_accumulate();
+			}
+
+			<PendingIssuance<T>>::kill();
+			let amount = Self::capped_issuance(owed);
+			let paid = if amount.is_zero() {
+				0
+			} else {
+				<CumulativeMinted<T>>::mutate(|minted| *minted = minted.saturating_add(amount));
+				let paid = Self::distribute(T::Currency::issue(amount));
+				Self::deposit_event(Event::InflationDistributed { amount });
+				paid
+			};
+
+			T::WeightInfo::on_initialize_mint(paid)
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight((
-		WeightInfo::<T>::set_config_with_balance(),
+		T::WeightInfo::set_issuing_amount(),
 		DispatchClass::Operational,
 		))]
 		pub fn set_issuing_amount(origin: OriginFor<T>, new: BalanceOf<T>) -> DispatchResult {
-			ensure_root(origin)?;
+			T::UpdateOrigin::ensure_origin(origin)?;
 			<IssuingAmount<T>>::put(new);
 			Ok(())
 		}
+
+		/// Set the percentage of supply inflation model.
+		/// Years of decay are counted from the block it is set in.
+		/// Clearing the model goes back to minting fixed `IssuingAmount`
+		#[pallet::weight((
+		T::WeightInfo::set_inflation_model(),
+		DispatchClass::Operational,
+		))]
+		pub fn set_inflation_model(
+			origin: OriginFor<T>,
+			model: Option<InflationModel>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(model) = model {
+				ensure!(
+					model.floor <= model.annual_rate,
+					Error::<T>::InvalidInflationModel
+				);
+			}
+
+			<Inflation<T>>::set(model);
+			<InflationStart<T>>::put(<frame_system::Pallet<T>>::block_number());
+
+			Self::deposit_event(Event::InflationModelChanged { model });
+			Ok(())
+		}
+
+		/// Temporarily stop minting without changing the configuration.
+		/// Nothing is owed for the blocks while paused
+		#[pallet::weight((T::WeightInfo::pause(), DispatchClass::Operational))]
+		pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::paused(), Error::<T>::AlreadyPaused);
+
+			<Paused<T>>::put(true);
+
+			Self::deposit_event(Event::MintingPaused);
+			Ok(())
+		}
+
+		/// Resume minting stopped by `pause`
+		#[pallet::weight((T::WeightInfo::resume(), DispatchClass::Operational))]
+		pub fn resume(origin: OriginFor<T>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(Self::paused(), Error::<T>::NotPaused);
+
+			<Paused<T>>::kill();
+
+			Self::deposit_event(Event::MintingResumed);
+			Ok(())
+		}
+
+		/// Set maximum total issuance up to which inflation is minted.
+		/// `None` removes the cap
+		#[pallet::weight((
+		T::WeightInfo::set_max_total_issuance(),
+		DispatchClass::Operational,
+		))]
+		pub fn set_max_total_issuance(
+			origin: OriginFor<T>,
+			cap: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			<MaxTotalIssuance<T>>::set(cap);
+			<CapReached<T>>::kill();
+
+			Self::deposit_event(Event::MaxTotalIssuanceChanged { cap });
+			Ok(())
+		}
+
+		/// Set destinations of minted inflation along with their share.
+		/// Shares must sum up to 100% unless the list is empty
+		#[pallet::weight((
+		T::WeightInfo::set_beneficiaries(beneficiaries.len() as u32),
+		DispatchClass::Operational,
+		))]
+		pub fn set_beneficiaries(
+			origin: OriginFor<T>,
+			beneficiaries: Vec<(InflationBeneficiaryOf<T>, Perbill)>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let beneficiaries = Self::validate_beneficiaries(beneficiaries)?;
+			<Beneficiaries<T>>::put(beneficiaries.clone());
+
+			Self::deposit_event(Event::BeneficiariesChanged { beneficiaries });
+			Ok(())
+		}
 	}
-}
 
-pub struct WeightInfo<T>(PhantomData<T>);
-impl<T: frame_system::Config> WeightInfo<T> {
-	pub fn set_config_with_balance() -> Weight {
-		Weight::from_ref_time(10_000_000).saturating_add(T::DbWeight::get().writes(1))
+	impl<T: Config> Pallet<T> {
+		pub fn validate_beneficiaries(
+			beneficiaries: Vec<(InflationBeneficiaryOf<T>, Perbill)>,
+		) -> Result<BeneficiariesOf<T>, Error<T>> {
+			let beneficiaries: BeneficiariesOf<T> = beneficiaries
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyBeneficiaries)?;
+
+			let total_parts = beneficiaries
+				.iter()
+				.map(|(_, share)| share.deconstruct() as u64)
+				.sum::<u64>();
+			ensure!(
+				beneficiaries.is_empty() || total_parts == Perbill::one().deconstruct() as u64,
+				Error::<T>::InvalidShares
+			);
+
+			Ok(beneficiaries)
+		}
+
+		/// Reduce amount to mint so that total issuance does not exceed the cap.
+		/// `CapReached` is recomputed from current total issuance on every minting,
+		/// so it is cleared once burns make room below the cap again.
+		/// Cap being reached is signaled each time it becomes reached
+		pub fn capped_issuance(amount: BalanceOf<T>) -> BalanceOf<T> {
+			let cap = match Self::max_total_issuance() {
+				Some(cap) => cap,
+				None => return amount,
+			};
+
+			let total_issuance = T::Currency::total_issuance();
+			let room = cap.saturating_sub(total_issuance);
+			let reached = room.is_zero() || (!amount.is_zero() && amount >= room);
+			if reached != Self::cap_reached() {
+				<CapReached<T>>::put(reached);
+				if reached {
+					Self::deposit_event(Event::MaxTotalIssuanceReached {
+						total_issuance: total_issuance.saturating_add(room),
+					});
+				}
+			}
+
+			amount.min(room)
+		}
+
+		/// Whether inflation owed for the period is minted in given block
+		pub fn is_minting_block(now: T::BlockNumber) -> bool {
+			let period = T::MintingPeriod::get();
+			period.is_zero() || (now % period).is_zero()
+		}
+
+		/// Split minted inflation among beneficiaries.
+		/// Last beneficiary also receive the remainder left by rounding.
+		/// Returns the number of beneficiaries paid
+		pub fn distribute(inflation: NegativeImbalanceOf<T>) -> u32 {
+			let beneficiaries = Self::beneficiaries();
+			if beneficiaries.is_empty() {
+				Self::pay(InflationBeneficiary::Treasury, inflation);
+				return 1;
+			}
+
+			let paid = beneficiaries.len() as u32;
+			let total = inflation.peek();
+			let last = beneficiaries.len().saturating_sub(1);
+			let mut remaining = inflation;
+			for (index, (beneficiary, share)) in beneficiaries.into_iter().enumerate() {
+				let part = if index == last {
+					sp_std::mem::replace(&mut remaining, NegativeImbalanceOf::<T>::zero())
+				} else {
+					let (part, rest) = remaining.split(share * total);
+					remaining = rest;
+					part
+				};
+				Self::pay(beneficiary, part);
+			}
+
+			paid
+		}
+
+		fn pay(beneficiary: InflationBeneficiaryOf<T>, amount: NegativeImbalanceOf<T>) {
+			Self::deposit_event(Event::Issued {
+				amount: amount.peek(),
+				beneficiary: beneficiary.clone(),
+			});
+
+			match beneficiary {
+				InflationBeneficiary::Treasury => T::Beneficiary::treasury(amount),
+				InflationBeneficiary::CollatorPot => T::Beneficiary::collator_pot(amount),
+				InflationBeneficiary::AirdropPot => T::Beneficiary::airdrop_pot(amount),
+				InflationBeneficiary::Account(who) => T::Currency::resolve_creating(&who, amount),
+			}
+		}
+
+		/// Number of blocks produced in a year at expected block time
+		pub fn blocks_per_year() -> u64 {
+			MILLISECS_PER_YEAR / T::ExpectedBlockTime::get().max(1)
+		}
+
+		/// Annual rate of inflation model at given block
+		pub fn annual_rate(now: T::BlockNumber) -> Option<sp_runtime::Perbill> {
+			let model = Self::inflation_model()?;
+			let elapsed: u64 = now
+				.saturating_sub(Self::inflation_start())
+				.unique_saturated_into();
+			let years = elapsed / Self::blocks_per_year().max(1);
+			Some(model.rate_after(years.unique_saturated_into()))
+		}
+
+		/// Annual rate of inflation at current block. Without inflation model,
+		/// it is the rate that fixed issuing amount represent of total issuance
+		pub fn current_rate() -> Perbill {
+			let now = <frame_system::Pallet<T>>::block_number();
+			Self::annual_rate(now).unwrap_or_else(|| {
+				Perbill::from_rational(
+					Self::uncapped_annual_issuance(now),
+					T::Currency::total_issuance(),
+				)
+			})
+		}
+
+		/// Amount that will be minted in the next minting block,
+		/// assuming total issuance does not change until then
+		pub fn next_issuance() -> BalanceOf<T> {
+			if Self::paused() {
+				return Zero::zero();
+			}
+
+			let next = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+			let period = T::MintingPeriod::get();
+			let remaining_blocks = if period.is_zero() {
+				Zero::zero()
+			} else {
+				(period - next % period) % period
+			};
+			let blocks: u64 = remaining_blocks.unique_saturated_into();
+			let owed = Self::block_issuance(next)
+				.saturating_mul(BalanceOf::<T>::unique_saturated_from(
+					blocks.saturating_add(1),
+				))
+				.saturating_add(Self::pending_issuance());
+
+			Self::within_cap(owed)
+		}
+
+		/// Amount minted during a year at current rate
+		pub fn projected_annual_issuance() -> BalanceOf<T> {
+			if Self::paused() {
+				return Zero::zero();
+			}
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			Self::within_cap(Self::uncapped_annual_issuance(now))
+		}
+
+		fn uncapped_annual_issuance(now: T::BlockNumber) -> BalanceOf<T> {
+			Self::block_issuance(now).saturating_mul(BalanceOf::<T>::unique_saturated_from(
+				Self::blocks_per_year(),
+			))
+		}
+
+		/// Part of amount that can be minted without exceeding the cap
+		fn within_cap(amount: BalanceOf<T>) -> BalanceOf<T> {
+			match Self::max_total_issuance() {
+				Some(cap) => amount.min(cap.saturating_sub(T::Currency::total_issuance())),
+				None => amount,
+			}
+		}
+
+		/// Amount owed for given block
+		pub fn block_issuance(now: T::BlockNumber) -> BalanceOf<T> {
+			match Self::annual_rate(now) {
+				Some(rate) => {
+					let annual = rate * T::Currency::total_issuance();
+					annual / BalanceOf::<T>::unique_saturated_from(Self::blocks_per_year().max(1))
+				}
+				None => Self::issuing_amount(),
+			}
+		}
 	}
 }
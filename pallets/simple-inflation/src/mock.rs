@@ -0,0 +1,125 @@
+use crate::{self as pallet_simple_inflation, NegativeImbalanceOf};
+use frame_support::traits::{ConstU128, ConstU32, ConstU64, Currency, GenesisBuild};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+pub type AccountId = u64;
+pub type Balance = u128;
+
+pub const TREASURY: AccountId = 1;
+pub const COLLATOR_POT: AccountId = 2;
+pub const ALICE: AccountId = 10;
+pub const BOB: AccountId = 11;
+pub const INITIAL_ISSUANCE: Balance = 1_000_000;
+
+/// Fixed amount minted per block without inflation model
+pub const ISSUING_AMOUNT: Balance = 100;
+/// Number of blocks between two mintings
+pub const MINTING_PERIOD: u64 = 10;
+/// Block time making a year last exactly `BLOCKS_PER_YEAR` blocks
+pub const BLOCKS_PER_YEAR: u64 = 1_000;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		SimpleInflation: pallet_simple_inflation::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+}
+
+/// Treasury and collator pot are plain accounts, airdrop pot falls back to treasury
+pub struct Beneficiary;
+
+impl pallet_simple_inflation::Beneficiary<NegativeImbalanceOf<Test>> for Beneficiary {
+	fn treasury(reward: NegativeImbalanceOf<Test>) {
+		Balances::resolve_creating(&TREASURY, reward);
+	}
+
+	fn collator_pot(reward: NegativeImbalanceOf<Test>) {
+		Balances::resolve_creating(&COLLATOR_POT, reward);
+	}
+}
+
+impl pallet_simple_inflation::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type IssuingAmount = ConstU128<ISSUING_AMOUNT>;
+	type ExpectedBlockTime =
+		ConstU64<{ pallet_simple_inflation::MILLISECS_PER_YEAR / BLOCKS_PER_YEAR }>;
+	type Beneficiary = Beneficiary;
+	type MaxBeneficiaries = ConstU32<3>;
+	type MintingPeriod = ConstU64<MINTING_PERIOD>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = pallet_simple_inflation::weights::SimpleInflationWeightInfo<Test>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::default()
+		.build_storage::<Test>()
+		.unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, INITIAL_ISSUANCE)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+
+	<pallet_simple_inflation::GenesisConfig<Test> as GenesisBuild<Test>>::assimilate_storage(
+		&pallet_simple_inflation::GenesisConfig::new(ISSUING_AMOUNT),
+		&mut storage,
+	)
+	.unwrap();
+
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,316 @@
+use crate::{mock::*, Error, Event, InflationBeneficiary, InflationModel};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, Hooks},
+};
+use sp_runtime::{DispatchError, Perbill};
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		SimpleInflation::on_initialize(System::block_number());
+	}
+}
+
+fn model(annual_rate: u32, yearly_decay: Option<u32>, floor: u32) -> InflationModel {
+	InflationModel {
+		annual_rate: Perbill::from_percent(annual_rate),
+		yearly_decay: yearly_decay.map(Perbill::from_percent),
+		floor: Perbill::from_percent(floor),
+	}
+}
+
+#[test]
+fn rate_decays_down_to_floor() {
+	let decaying = model(10, Some(50), 2);
+	assert_eq!(decaying.rate_after(0), Perbill::from_percent(10));
+	assert_eq!(decaying.rate_after(1), Perbill::from_percent(5));
+	assert_eq!(
+		decaying.rate_after(2),
+		Perbill::from_rational(25u32, 1000u32)
+	);
+	assert_eq!(decaying.rate_after(3), Perbill::from_percent(2));
+	assert_eq!(decaying.rate_after(100), Perbill::from_percent(2));
+
+	let constant = model(10, None, 0);
+	assert_eq!(constant.rate_after(100), Perbill::from_percent(10));
+}
+
+#[test]
+fn set_inflation_model() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			SimpleInflation::set_inflation_model(
+				RuntimeOrigin::signed(ALICE),
+				Some(model(10, None, 0))
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			SimpleInflation::set_inflation_model(RuntimeOrigin::root(), Some(model(2, None, 5))),
+			Error::<Test>::InvalidInflationModel
+		);
+
+		run_to_block(5);
+		assert_ok!(SimpleInflation::set_inflation_model(
+			RuntimeOrigin::root(),
+			Some(model(10, None, 0))
+		));
+		System::assert_last_event(
+			Event::InflationModelChanged {
+				model: Some(model(10, None, 0)),
+			}
+			.into(),
+		);
+		assert_eq!(SimpleInflation::inflation_start(), 5);
+
+		assert_ok!(SimpleInflation::set_inflation_model(
+			RuntimeOrigin::root(),
+			None
+		));
+		assert_eq!(SimpleInflation::inflation_model(), None);
+		assert_eq!(SimpleInflation::block_issuance(5), ISSUING_AMOUNT);
+	});
+}
+
+#[test]
+fn block_issuance_decays_yearly() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SimpleInflation::set_inflation_model(
+			RuntimeOrigin::root(),
+			Some(model(10, Some(50), 2))
+		));
+		assert_eq!(SimpleInflation::blocks_per_year(), BLOCKS_PER_YEAR);
+
+		// 10% of 1_000_000 over 1_000 blocks
+		assert_eq!(SimpleInflation::block_issuance(1), 100);
+		assert_eq!(SimpleInflation::block_issuance(BLOCKS_PER_YEAR), 100);
+		assert_eq!(SimpleInflation::block_issuance(BLOCKS_PER_YEAR + 1), 50);
+		assert_eq!(SimpleInflation::block_issuance(2 * BLOCKS_PER_YEAR + 1), 25);
+		assert_eq!(SimpleInflation::block_issuance(3 * BLOCKS_PER_YEAR + 1), 20);
+		assert_eq!(SimpleInflation::block_issuance(100 * BLOCKS_PER_YEAR), 20);
+		assert_eq!(
+			SimpleInflation::annual_rate(100 * BLOCKS_PER_YEAR),
+			Some(Perbill::from_percent(2))
+		);
+	});
+}
+
+#[test]
+fn is_minting_block() {
+	new_test_ext().execute_with(|| {
+		assert!(SimpleInflation::is_minting_block(0));
+		assert!(SimpleInflation::is_minting_block(MINTING_PERIOD));
+		assert!(SimpleInflation::is_minting_block(2 * MINTING_PERIOD));
+		assert!(!SimpleInflation::is_minting_block(1));
+		assert!(!SimpleInflation::is_minting_block(MINTING_PERIOD - 1));
+		assert!(!SimpleInflation::is_minting_block(MINTING_PERIOD + 1));
+	});
+}
+
+#[test]
+fn fixed_amount_minted_once_per_period() {
+	new_test_ext().execute_with(|| {
+		run_to_block(MINTING_PERIOD - 1);
+		assert_eq!(SimpleInflation::pending_issuance(), 800);
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE);
+
+		run_to_block(MINTING_PERIOD);
+		assert_eq!(SimpleInflation::pending_issuance(), 0);
+		assert_eq!(Balances::free_balance(TREASURY), 900);
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + 900);
+		assert_eq!(SimpleInflation::cumulative_minted(), 900);
+		System::assert_has_event(
+			Event::Issued {
+				amount: 900,
+				beneficiary: InflationBeneficiary::Treasury,
+			}
+			.into(),
+		);
+		System::assert_last_event(Event::InflationDistributed { amount: 900 }.into());
+
+		assert_ok!(SimpleInflation::set_issuing_amount(
+			RuntimeOrigin::root(),
+			50
+		));
+		run_to_block(2 * MINTING_PERIOD);
+		assert_eq!(Balances::free_balance(TREASURY), 900 + 500);
+	});
+}
+
+#[test]
+fn percentage_of_supply_minted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SimpleInflation::set_inflation_model(
+			RuntimeOrigin::root(),
+			Some(model(20, None, 0))
+		));
+
+		run_to_block(MINTING_PERIOD);
+		assert_eq!(Balances::free_balance(TREASURY), 9 * 200);
+		assert_eq!(SimpleInflation::current_rate(), Perbill::from_percent(20));
+	});
+}
+
+#[test]
+fn next_issuance() {
+	new_test_ext().execute_with(|| {
+		// Blocks 2 to 10 are owed in the first minting block
+		assert_eq!(SimpleInflation::next_issuance(), 900);
+		run_to_block(MINTING_PERIOD);
+		assert_eq!(SimpleInflation::cumulative_minted(), 900);
+
+		assert_eq!(SimpleInflation::next_issuance(), 1_000);
+		run_to_block(MINTING_PERIOD + 5);
+		assert_eq!(SimpleInflation::next_issuance(), 1_000);
+		run_to_block(2 * MINTING_PERIOD);
+		assert_eq!(SimpleInflation::cumulative_minted(), 1_900);
+
+		assert_ok!(SimpleInflation::pause(RuntimeOrigin::root()));
+		assert_eq!(SimpleInflation::next_issuance(), 0);
+		assert_eq!(SimpleInflation::projected_annual_issuance(), 0);
+	});
+}
+
+#[test]
+fn minting_stops_at_max_total_issuance() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			SimpleInflation::set_max_total_issuance(RuntimeOrigin::signed(ALICE), None),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(SimpleInflation::set_max_total_issuance(
+			RuntimeOrigin::root(),
+			Some(INITIAL_ISSUANCE + 500)
+		));
+		assert_eq!(SimpleInflation::next_issuance(), 500);
+
+		run_to_block(MINTING_PERIOD);
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + 500);
+		assert!(SimpleInflation::cap_reached());
+		System::assert_has_event(
+			Event::MaxTotalIssuanceReached {
+				total_issuance: INITIAL_ISSUANCE + 500,
+			}
+			.into(),
+		);
+		assert_eq!(SimpleInflation::next_issuance(), 0);
+
+		run_to_block(2 * MINTING_PERIOD);
+		assert_eq!(SimpleInflation::cumulative_minted(), 500);
+
+		// Raising the cap resumes minting up to the new cap
+		assert_ok!(SimpleInflation::set_max_total_issuance(
+			RuntimeOrigin::root(),
+			Some(INITIAL_ISSUANCE + 1_000)
+		));
+		assert!(!SimpleInflation::cap_reached());
+		run_to_block(3 * MINTING_PERIOD);
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + 1_000);
+		assert!(SimpleInflation::cap_reached());
+	});
+}
+
+#[test]
+fn split_among_beneficiaries() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			SimpleInflation::set_beneficiaries(
+				RuntimeOrigin::root(),
+				vec![
+					(InflationBeneficiary::CollatorPot, Perbill::from_percent(50)),
+					(InflationBeneficiary::Treasury, Perbill::from_percent(40)),
+				]
+			),
+			Error::<Test>::InvalidShares
+		);
+		assert_noop!(
+			SimpleInflation::set_beneficiaries(
+				RuntimeOrigin::root(),
+				vec![(InflationBeneficiary::Treasury, Perbill::from_percent(25)); 4]
+			),
+			Error::<Test>::TooManyBeneficiaries
+		);
+
+		let beneficiaries = vec![
+			(InflationBeneficiary::CollatorPot, Perbill::from_percent(50)),
+			(
+				InflationBeneficiary::Account(BOB),
+				Perbill::from_percent(30),
+			),
+			(InflationBeneficiary::AirdropPot, Perbill::from_percent(20)),
+		];
+		assert_ok!(SimpleInflation::set_beneficiaries(
+			RuntimeOrigin::root(),
+			beneficiaries.clone()
+		));
+		System::assert_last_event(
+			Event::BeneficiariesChanged {
+				beneficiaries: beneficiaries.try_into().unwrap(),
+			}
+			.into(),
+		);
+
+		run_to_block(MINTING_PERIOD);
+		assert_eq!(Balances::free_balance(COLLATOR_POT), 450);
+		assert_eq!(Balances::free_balance(BOB), 270);
+		// Mock runtime has no airdrop pot
+		assert_eq!(Balances::free_balance(TREASURY), 180);
+	});
+}
+
+#[test]
+fn nothing_is_owed_while_paused() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			SimpleInflation::resume(RuntimeOrigin::root()),
+			Error::<Test>::NotPaused
+		);
+		assert_ok!(SimpleInflation::pause(RuntimeOrigin::root()));
+		System::assert_last_event(Event::MintingPaused.into());
+		assert_noop!(
+			SimpleInflation::pause(RuntimeOrigin::root()),
+			Error::<Test>::AlreadyPaused
+		);
+
+		run_to_block(MINTING_PERIOD);
+		assert_eq!(SimpleInflation::pending_issuance(), 0);
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE);
+
+		assert_ok!(SimpleInflation::resume(RuntimeOrigin::root()));
+		System::assert_last_event(Event::MintingResumed.into());
+		run_to_block(2 * MINTING_PERIOD);
+		assert_eq!(Balances::free_balance(TREASURY), 1_000);
+	});
+}
+
+#[test]
+fn cap_reached_is_recomputed_after_burns() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(SimpleInflation::set_max_total_issuance(
+			RuntimeOrigin::root(),
+			Some(INITIAL_ISSUANCE + 500)
+		));
+		run_to_block(MINTING_PERIOD);
+		assert!(SimpleInflation::cap_reached());
+
+		// Burning makes room for a whole period below the cap
+		let _ = Balances::slash(&ALICE, 2_000);
+		run_to_block(2 * MINTING_PERIOD);
+		assert!(!SimpleInflation::cap_reached());
+		assert_eq!(SimpleInflation::cumulative_minted(), 1_500);
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE - 500);
+
+		// Reaching the cap again is signaled again
+		System::reset_events();
+		run_to_block(3 * MINTING_PERIOD);
+		assert!(SimpleInflation::cap_reached());
+		assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + 500);
+		System::assert_has_event(
+			Event::MaxTotalIssuanceReached {
+				total_issuance: INITIAL_ISSUANCE + 500,
+			}
+			.into(),
+		);
+	});
+}
@@ -0,0 +1,46 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::Saturating, Perbill};
+
+/// Inflation defined as an annual percentage of total issuance
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct InflationModel {
+	/// Percent of total issuance minted during the first year
+	pub annual_rate: Perbill,
+	/// Part by which the annual rate is reduced every year
+	pub yearly_decay: Option<Perbill>,
+	/// Annual rate never decays below this value
+	pub floor: Perbill,
+}
+
+impl InflationModel {
+	/// Annual rate applicable after given number of complete years
+	pub fn rate_after(&self, years: u32) -> Perbill {
+		let rate = match self.yearly_decay {
+			Some(decay) => self
+				.annual_rate
+				.saturating_mul(decay.left_from_one().saturating_pow(years as usize)),
+			None => self.annual_rate,
+		};
+		rate.max(self.floor)
+	}
+}
+
+/// Destination of a share of minted inflation
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum InflationBeneficiary<AccountId> {
+	/// Treasury of the chain
+	Treasury,
+	/// Reward pot of collators
+	CollatorPot,
+	/// Account funding the airdrop
+	AirdropPot,
+	/// Any other account
+	Account(AccountId),
+}
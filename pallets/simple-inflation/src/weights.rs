@@ -0,0 +1,117 @@
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub trait WeightInfo {
+	fn on_initialize_accumulate() -> Weight;
+	fn on_initialize_mint(b: u32) -> Weight;
+	fn set_issuing_amount() -> Weight;
+	fn set_inflation_model() -> Weight;
+	fn set_beneficiaries(b: u32) -> Weight;
+	fn set_max_total_issuance() -> Weight;
+	fn pause() -> Weight;
+	fn resume() -> Weight;
+}
+
+/// Weight functions for `pallet_simple_inflation`.
+///
+/// These weights have not been generated on reference hardware yet. Execution times are
+/// estimates and storage reads and writes follow the code. Regenerate this file with
+/// `benchmark pallet --pallet pallet_simple_inflation --extrinsic '*'`
+/// before relying on the numbers.
+pub struct SimpleInflationWeightInfo<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SimpleInflationWeightInfo<T> {
+	// Storage: SimpleInflation Paused (r:1 w:0)
+	// Storage: SimpleInflation PendingIssuance (r:1 w:1)
+	// Storage: SimpleInflation Inflation (r:1 w:0)
+	// Storage: SimpleInflation InflationStart (r:1 w:0)
+	// Storage: Balances TotalIssuance (r:1 w:0)
+	fn on_initialize_accumulate() -> Weight {
+		Weight::from_ref_time(10_412_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: SimpleInflation Paused (r:1 w:0)
+	// Storage: SimpleInflation PendingIssuance (r:1 w:1)
+	// Storage: SimpleInflation Inflation (r:1 w:0)
+	// Storage: SimpleInflation InflationStart (r:1 w:0)
+	// Storage: Balances TotalIssuance (r:1 w:1)
+	// Storage: SimpleInflation MaxTotalIssuance (r:1 w:0)
+	// Storage: SimpleInflation CapReached (r:1 w:1)
+	// Storage: SimpleInflation CumulativeMinted (r:1 w:1)
+	// Storage: SimpleInflation Beneficiaries (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	/// The range of component `b` is `[1, 8]`.
+	fn on_initialize_mint(b: u32) -> Weight {
+		Weight::from_ref_time(34_185_000)
+			.saturating_add(Weight::from_ref_time(17_946_000).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().reads(1).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().writes(1).saturating_mul(b as u64))
+	}
+	// Storage: SimpleInflation IssuingAmount (r:0 w:1)
+	fn set_issuing_amount() -> Weight {
+		Weight::from_ref_time(10_000_000).saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: SimpleInflation Inflation (r:0 w:1)
+	// Storage: SimpleInflation InflationStart (r:0 w:1)
+	fn set_inflation_model() -> Weight {
+		Weight::from_ref_time(15_293_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: SimpleInflation Beneficiaries (r:0 w:1)
+	/// The range of component `b` is `[0, 8]`.
+	fn set_beneficiaries(b: u32) -> Weight {
+		Weight::from_ref_time(14_806_000)
+			.saturating_add(Weight::from_ref_time(312_000).saturating_mul(b as u64))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	// Storage: SimpleInflation MaxTotalIssuance (r:0 w:1)
+	// Storage: SimpleInflation CapReached (r:0 w:1)
+	fn set_max_total_issuance() -> Weight {
+		Weight::from_ref_time(14_127_000)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	// Storage: SimpleInflation Paused (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn pause() -> Weight {
+		Weight::from_ref_time(14_031_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// Storage: SimpleInflation Paused (r:1 w:1)
+	// Storage: System Number (r:1 w:0)
+	// Storage: System ExecutionPhase (r:1 w:0)
+	// Storage: System EventCount (r:1 w:1)
+	// Storage: System Events (r:1 w:1)
+	fn resume() -> Weight {
+		Weight::from_ref_time(13_866_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+}
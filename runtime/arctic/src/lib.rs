@@ -65,7 +65,7 @@ use sp_std::boxed::Box;
 pub mod constants;
 pub mod impls;
 pub use constants::{currency, time::*};
-pub use impls::DealWithFees;
+pub type DealWithFees = pallet_fees_split::DistributeFees<Runtime>;
 
 pub type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
@@ -107,7 +107,7 @@ use pallet_contracts::{weights::WeightInfo, Migration};
 use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
 use pallet_evm::{Account as EVMAccount, EnsureAddressTruncated, HashedAddressMapping, Runner};
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
+use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 use sp_runtime::traits::AccountIdConversion;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -473,7 +473,7 @@ pub type WeightToFee = constants::fee::WeightToFee;
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
+	type OnChargeTransaction = pallet_fees_split::ChargeTransactionFees<Runtime>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = constants::fee::WeightToFee;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
@@ -525,7 +525,7 @@ impl pallet_evm::Config for Runtime {
 	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
-	type OnChargeTransaction = ();
+	type OnChargeTransaction = pallet_fees_split::DistributeEvmFees<Runtime>;
 	type FindAuthor = FindAuthorTruncated<Aura>;
 }
 
@@ -651,6 +651,17 @@ impl pallet_simple_inflation::Beneficiary<NegativeImbalance> for Beneficiary {
 	fn treasury(reward: NegativeImbalance) {
 		Balances::resolve_creating(&TreasuryPalletId::get().into_account_truncating(), reward);
 	}
+
+	fn collator_pot(reward: NegativeImbalance) {
+		Balances::resolve_creating(&PotId::get().into_account_truncating(), reward);
+	}
+
+	fn airdrop_pot(reward: NegativeImbalance) {
+		match Airdrop::try_get_creditor_account() {
+			Some(creditor) => Balances::resolve_creating(&creditor, reward),
+			None => Self::treasury(reward),
+		}
+	}
 }
 
 parameter_types! {
@@ -658,12 +669,34 @@ parameter_types! {
 }
 
 impl pallet_simple_inflation::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type IssuingAmount = IssuingAmount;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
 	type Beneficiary = Beneficiary;
+	type MaxBeneficiaries = ConstU32<8>;
+	type MintingPeriod = ConstU32<HOURS>;
+	type UpdateOrigin = MoreThanHalfCouncil;
+	type WeightInfo = pallet_simple_inflation::weights::SimpleInflationWeightInfo<Runtime>;
 }
 
-impl pallet_fees_split::Config for Runtime {}
+parameter_types! {
+	pub const RebateDeposit: Balance = 100 * currency::DOLLARS;
+}
+
+impl pallet_fees_split::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type Treasury = Treasury;
+	type Author = impls::Author;
+	type CollatorPot = impls::CollatorPot;
+	type UpdateOrigin = MoreThanHalfCouncil;
+	type MaxRecipients = ConstU32<16>;
+	type LedgerPeriod = ConstU32<DAYS>;
+	type LedgerRetainedPeriods = ConstU32<30>;
+	type CalledContract = pallet_fees_split::RuntimeContracts<Runtime>;
+	type RebateDeposit = RebateDeposit;
+}
 
 impl pallet_utility::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -1099,7 +1132,7 @@ impl pallet_randomness_collective_flip::Config for Runtime {}
 const AIRDROP_VESTING_TERMS: pallet_airdrop::VestingTerms = pallet_airdrop::VestingTerms {
 	defi_instant_percentage: 100,
 	non_defi_instant_percentage: 100,
-	vesting_period: 7776000,
+	vesting_period: pallet_airdrop::VestingPeriod::Blocks(7776000),
 };
 
 impl pallet_airdrop::Config for Runtime {
@@ -1109,6 +1142,10 @@ impl pallet_airdrop::Config for Runtime {
 	type BalanceTypeConversion = ConvertInto;
 	type MerkelProofValidator = pallet_airdrop::merkle::AirdropMerkleValidator<Runtime>;
 	type MaxProofSize = ConstU32<21>;
+	type MaxServerAccounts = ConstU32<10>;
+	type BlocksPerDay = ConstU32<DAYS>;
+	type UnixTime = Timestamp;
+	type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
 	const VESTING_TERMS: pallet_airdrop::VestingTerms = AIRDROP_VESTING_TERMS;
 }
 
@@ -1384,8 +1421,8 @@ construct_runtime!(
 		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>} = 21,
 		Vesting: pallet_vesting::{Pallet, Call, Storage, Config<T>, Event<T>} = 22,
 		TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>} = 23,
-		SimpleInflation: pallet_simple_inflation::{Pallet, Call, Storage, Config<T>} = 24,
-		FeesSplit: pallet_fees_split::{Pallet, Call, Storage, Config<T>} = 25,
+		SimpleInflation: pallet_simple_inflation::{Pallet, Call, Storage, Config<T>, Event<T>} = 24,
+		FeesSplit: pallet_fees_split::{Pallet, Call, Storage, Config<T>, Event<T>} = 25,
 		Airdrop: pallet_airdrop::{Pallet, Call, Storage, Config<T>, Event<T>} = 26,
 
 		// Contracts stuff
@@ -1547,9 +1584,15 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
 		match self {
 			call @ RuntimeCall::Ethereum(pallet_ethereum::Call::transact { .. }) => {
-				Some(call.dispatch(RuntimeOrigin::from(
+				FeesSplit::note_called_contract(&call);
+				pallet_fees_split::RuntimeContracts::<Runtime>::note_ethereum_deployment(
+					&call, info,
+				);
+				let result = call.dispatch(RuntimeOrigin::from(
 					pallet_ethereum::RawOrigin::EthereumTransaction(info),
-				)))
+				));
+				FeesSplit::record_deployment();
+				Some(result)
 			}
 			_ => None,
 		}
@@ -1798,6 +1841,38 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_fees_split_runtime_api::FeesSplitApi<Block, AccountId, Balance> for Runtime {
+		fn cumulative_fees(
+			recipient: pallet_fees_split::FeeRecipient<AccountId>,
+		) -> pallet_fees_split::FeeTotals<Balance> {
+			FeesSplit::cumulative_fees(recipient)
+		}
+
+		fn period_fees(
+			recipient: pallet_fees_split::FeeRecipient<AccountId>,
+		) -> Vec<(u32, pallet_fees_split::FeeTotals<Balance>)> {
+			FeesSplit::recent_period_fees(recipient)
+		}
+	}
+
+	impl pallet_simple_inflation_runtime_api::InflationApi<Block, Balance> for Runtime {
+		fn current_rate() -> Perbill {
+			SimpleInflation::current_rate()
+		}
+
+		fn next_issuance() -> Balance {
+			SimpleInflation::next_issuance()
+		}
+
+		fn projected_annual_issuance() -> Balance {
+			SimpleInflation::projected_annual_issuance()
+		}
+
+		fn cumulative_minted() -> Balance {
+			SimpleInflation::cumulative_minted()
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -1829,6 +1904,8 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_evm, EVM);
 			add_benchmark!(params, batches, pallet_vesting, Vesting);
+			add_benchmark!(params, batches, pallet_simple_inflation, SimpleInflation);
+			add_benchmark!(params, batches, pallet_airdrop, Airdrop);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -1848,6 +1925,8 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_evm, EVM);
 			list_benchmark!(list, extra, pallet_vesting, Vesting);
+			list_benchmark!(list, extra, pallet_simple_inflation, SimpleInflation);
+			list_benchmark!(list, extra, pallet_airdrop, Airdrop);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
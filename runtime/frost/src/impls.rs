@@ -1,5 +1,5 @@
-use crate::{Authorship, Balances, FeesSplit, NegativeImbalance, Treasury};
-use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use crate::{Authorship, Balances, NegativeImbalance};
+use frame_support::traits::{Currency, OnUnbalanced};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -10,23 +10,6 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 	}
 }
 
-pub struct DealWithFees;
-impl OnUnbalanced<NegativeImbalance> for DealWithFees {
-	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
-		let treasury_cut = FeesSplit::treasury_cut_percent();
-		let author_cut = 100 - treasury_cut;
-
-		if let Some(fees) = fees_then_tips.next() {
-			let mut split = fees.ration(treasury_cut, author_cut);
-			if let Some(tips) = fees_then_tips.next() {
-				tips.ration_merge_into(0, 100, &mut split);
-			}
-			Treasury::on_unbalanced(split.0);
-			Author::on_unbalanced(split.1);
-		}
-	}
-}
-
 #[cfg(test)]
 mod tests {
 	use crate::sp_api_hidden_includes_construct_runtime::hidden_include::weights::WeightToFee;
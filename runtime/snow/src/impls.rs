@@ -1,5 +1,6 @@
-use crate::{Authorship, Balances, FeesSplit, NegativeImbalance, Treasury};
-use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use crate::{AccountId, Authorship, Balances, NegativeImbalance, PotId};
+use frame_support::traits::{Currency, Get, OnUnbalanced};
+use sp_runtime::traits::AccountIdConversion;
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -9,20 +10,11 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 		}
 	}
 }
-pub struct DealWithFees;
-impl OnUnbalanced<NegativeImbalance> for DealWithFees {
-	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
-		let treasury_cut = FeesSplit::treasury_cut_percent();
-		let author_cut = 100 - treasury_cut;
-
-		if let Some(fees) = fees_then_tips.next() {
-			let mut split = fees.ration(treasury_cut, author_cut);
-			if let Some(tips) = fees_then_tips.next() {
-				tips.ration_merge_into(0, 100, &mut split);
-			}
-			Treasury::on_unbalanced(split.0);
-			Author::on_unbalanced(split.1);
-		}
+/// Account of collator reward pot
+pub struct CollatorPot;
+impl Get<Option<AccountId>> for CollatorPot {
+	fn get() -> Option<AccountId> {
+		Some(PotId::get().into_account_truncating())
 	}
 }
 